@@ -0,0 +1,327 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{cross_check, Error, Part, Variant};
+
+/// A puzzle input with the answers recorded for it. Inputs come from a day's
+/// `inputs/` directory or from the worked examples in its `README.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownInput {
+    pub name: String,
+    pub path: PathBuf,
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl KnownInput {
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        return match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        };
+    }
+
+    fn set_answer(&mut self, key: &str, answer: String) -> Result<(), String> {
+        match key {
+            "part_one" => self.part_one = Some(answer),
+            "part_two" => self.part_two = Some(answer),
+            _ => return Err(format!("unknown answer `{}`", key)),
+        }
+        return Ok(());
+    }
+
+    /// Cross-checks every variant of `part` on this input and compares the
+    /// result with the recorded answer, ignoring trailing whitespace.
+    pub fn verify(&self, variants: &[Variant], part: Part) -> Result<String, Error> {
+        let answer = cross_check(variants, part, &self.input)?;
+
+        return match self.answer(part) {
+            Some(expected) if expected.trim_end() != answer.trim_end() => Err(format!(
+                "part {} answered {:?}, expected {:?}",
+                part, answer, expected
+            )
+            .into()),
+            _ => Ok(answer),
+        };
+    }
+}
+
+/// Lists every `inputs/<name>.txt` in `dir` followed by the examples in
+/// `dir/README.md`, along with the answers recorded for them in
+/// `dir/answers.toml`:
+///
+/// ```toml
+/// [default]
+/// part_one = 24000
+/// part_two = "45000"
+/// ```
+pub fn known_inputs(dir: impl AsRef<Path>) -> Result<Vec<KnownInput>, Error> {
+    let dir = dir.as_ref();
+    let mut inputs: Vec<KnownInput> = Vec::new();
+
+    let inputs_dir = dir.join("inputs");
+    let entries = fs::read_dir(&inputs_dir)
+        .map_err(|e| format!("could not read {}: {}", inputs_dir.display(), e))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        inputs.push(KnownInput {
+            name: name.to_string(),
+            input: read(&path)?,
+            path: path.clone(),
+            part_one: None,
+            part_two: None,
+        });
+    }
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let readme_path = dir.join("README.md");
+    if readme_path.exists() {
+        for example in readme_examples(&readme_path, &read(&readme_path)?)? {
+            if inputs.iter().any(|i| i.name == example.name) {
+                return Err(format!(
+                    "{}: example `{}` has the same name as an input",
+                    readme_path.display(),
+                    example.name
+                )
+                .into());
+            }
+            inputs.push(example);
+        }
+    }
+
+    let manifest_path = dir.join("answers.toml");
+    let manifest: toml::Table = match fs::read_to_string(&manifest_path) {
+        Ok(manifest) => manifest
+            .parse()
+            .map_err(|e| format!("invalid {}: {}", manifest_path.display(), e))?,
+        Err(_) => toml::Table::new(),
+    };
+
+    for (name, answers) in &manifest {
+        let known = inputs
+            .iter_mut()
+            .find(|i| &i.name == name)
+            .ok_or_else(|| format!("{}: no input named `{}`", manifest_path.display(), name))?;
+        let answers = answers
+            .as_table()
+            .ok_or_else(|| format!("{}: [{}] must be a table", manifest_path.display(), name))?;
+
+        for (key, value) in answers {
+            let answer = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Integer(n) => n.to_string(),
+                _ => {
+                    return Err(format!(
+                        "{}: {}.{} must be a string or an integer",
+                        manifest_path.display(),
+                        name,
+                        key
+                    )
+                    .into())
+                }
+            };
+            known
+                .set_answer(key, answer)
+                .map_err(|e| format!("{}: [{}] {}", manifest_path.display(), name, e))?;
+        }
+    }
+
+    return Ok(inputs);
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    return fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e).into());
+}
+
+// Examples are fenced blocks tagged `example`, optionally followed by a name
+// and short answers:
+//
+//     ```example part_one=24000 part_two=45000
+//
+// Answers spanning several lines go in an `answer` block after the example:
+//
+//     ```answer part_two
+fn readme_examples(path: &Path, readme: &str) -> Result<Vec<KnownInput>, Error> {
+    let mut examples: Vec<KnownInput> = Vec::new();
+    let mut lines = readme.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let Some(info) = line.trim_start().strip_prefix("```") else {
+            continue;
+        };
+        let error = |message: String| format!("{}:{}: {}", path.display(), number + 1, message);
+
+        let mut block = String::new();
+        loop {
+            match lines.next() {
+                Some((_, line)) if line.trim_start().starts_with("```") => break,
+                Some((_, line)) => {
+                    block.push_str(line);
+                    block.push('\n');
+                }
+                None => return Err(error("unterminated code block".to_string()).into()),
+            }
+        }
+
+        let mut words = info.split_whitespace();
+        match words.next() {
+            Some("example") => {
+                let mut example = KnownInput {
+                    name: String::new(),
+                    path: path.to_path_buf(),
+                    input: block,
+                    part_one: None,
+                    part_two: None,
+                };
+                for word in words {
+                    match word.split_once('=') {
+                        Some((key, answer)) => {
+                            example.set_answer(key, answer.to_string()).map_err(error)?
+                        }
+                        None => example.name = word.to_string(),
+                    }
+                }
+                if example.name.is_empty() {
+                    example.name = match examples.len() {
+                        0 => "example".to_string(),
+                        n => format!("example-{}", n + 1),
+                    };
+                }
+                if examples.iter().any(|e| e.name == example.name) {
+                    return Err(error(format!("duplicate example `{}`", example.name)).into());
+                }
+                examples.push(example);
+            }
+            Some("answer") => {
+                let key = words.next().unwrap_or_default();
+                let example = examples
+                    .last_mut()
+                    .ok_or_else(|| error("answer block before any example".to_string()))?;
+                example.set_answer(key, block).map_err(error)?;
+            }
+            _ => continue,
+        }
+    }
+
+    return Ok(examples);
+}
+
+/// Verifies every known input in `dir` that has a recorded answer.
+pub fn check_known_inputs(dir: impl AsRef<Path>, variants: &[Variant]) -> Result<(), Error> {
+    for known in known_inputs(dir)? {
+        for part in Part::ALL {
+            if known.answer(part).is_some() {
+                known
+                    .verify(variants, part)
+                    .map_err(|e| format!("{}: {}", known.name, e))?;
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    const VARIANTS: &[Variant] = &[
+        Variant::new(Part::One, "length", |i| Ok(i.trim_end().len().to_string())),
+        Variant::new(Part::Two, "lines", |i| Ok(i.lines().count().to_string())),
+    ];
+
+    // A day's directory under the temp dir, removed when the test ends.
+    struct Fixture {
+        dir: PathBuf,
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn fixture(name: &str, manifest: &str) -> Fixture {
+        let dir = env::temp_dir().join(format!("aoc-common-{}-{}", name, std::process::id()));
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::write(dir.join("inputs/example.txt"), "abc\nde\n").unwrap();
+        fs::write(dir.join("inputs/other.txt"), "a\n").unwrap();
+        fs::write(dir.join("answers.toml"), manifest).unwrap();
+        return Fixture { dir };
+    }
+
+    #[test]
+    fn known_inputs_reads_manifest() {
+        let fixture = fixture("reads", "[example]\npart_one = 6\npart_two = \"2\"\n");
+        let inputs = known_inputs(&fixture.dir).unwrap();
+
+        assert_eq!(2, inputs.len());
+        assert_eq!(
+            ("example", Some("6"), Some("2")),
+            (
+                inputs[0].name.as_str(),
+                inputs[0].answer(Part::One),
+                inputs[0].answer(Part::Two)
+            )
+        );
+        assert_eq!(
+            ("other", None),
+            (inputs[1].name.as_str(), inputs[1].answer(Part::One))
+        );
+        check_known_inputs(&fixture.dir, VARIANTS).unwrap();
+    }
+
+    #[test]
+    fn check_known_inputs_reports_wrong_answer() {
+        let fixture = fixture("wrong", "[example]\npart_two = 3\n");
+        let err = check_known_inputs(&fixture.dir, VARIANTS).unwrap_err();
+        assert_eq!(
+            "example: part 2 answered \"2\", expected \"3\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn known_inputs_rejects_missing_input() {
+        let fixture = fixture("missing", "[mine]\npart_one = 1\n");
+        assert!(known_inputs(&fixture.dir).is_err());
+    }
+
+    #[test]
+    fn readme_examples_reads_blocks_and_answers() {
+        let readme = "For example:\n\n```example part_one=5\nab\ncd\n```\n\n\
+                      Then:\n\n```answer part_two\n1\n2\n```\n\n```text\nnot an example\n```\n\n\
+                      ```example tiny part_two=1\nx\n```\n";
+        let examples = readme_examples(Path::new("README.md"), readme).unwrap();
+
+        assert_eq!(2, examples.len());
+        assert_eq!("example", examples[0].name);
+        assert_eq!("ab\ncd\n", examples[0].input);
+        assert_eq!(Some("5"), examples[0].answer(Part::One));
+        assert_eq!(Some("1\n2\n"), examples[0].answer(Part::Two));
+        assert_eq!("tiny", examples[1].name);
+        assert_eq!("x\n", examples[1].input);
+    }
+
+    #[test]
+    fn readme_examples_reports_unknown_answer() {
+        let err = readme_examples(Path::new("README.md"), "\n```example part_three=1\n```\n");
+        assert_eq!(
+            "README.md:2: unknown answer `part_three`",
+            err.unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::{io::Write, ops::Range};
+
+use crate::{Error, Part};
+
+/// Writes a valid puzzle input of roughly `size` records (elves, moves, rows
+/// and so on, depending on the day) and returns whatever answers are known
+/// from how it was built.
+pub type Generator = fn(size: usize, rng: &mut Rng, out: &mut dyn Write) -> Result<Answers, Error>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn new(part_one: impl ToString, part_two: impl ToString) -> Self {
+        return Answers {
+            part_one: Some(part_one.to_string()),
+            part_two: Some(part_two.to_string()),
+        };
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        return match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        };
+    }
+}
+
+/// A small SplitMix64 generator, so that a seed gives the same input on every
+/// platform and release.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        return Rng(seed);
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        return z ^ (z >> 31);
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range {:?}", range);
+        return range.start + self.next_u64() % (range.end - range.start);
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        return self.range(0..len as u64) as usize;
+    }
+
+    /// True one time in `n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        return self.range(0..n) == 0;
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.index(items.len())];
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rng_is_reproducible() {
+        let first: Vec<u64> = (0..4).map(|_| Rng::new(7).next_u64()).collect();
+        assert!(first.windows(2).all(|w| w[0] == w[1]));
+
+        let mut rng = Rng::new(7);
+        assert!((0..1000).all(|_| (3..9).contains(&rng.range(3..9))));
+    }
+}
@@ -0,0 +1,307 @@
+use alloc::{format, vec, vec::Vec};
+use core::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::ParseError;
+
+/// A cell's position in a [`Grid`], counting rows down and columns across from
+/// the top left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub const fn new(row: usize, col: usize) -> Self {
+        return Point { row, col };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// The change in row and column of one step this way.
+    pub fn offset(&self) -> (isize, isize) {
+        return match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        };
+    }
+}
+
+/// A rectangle of cells stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells in row order. Panics unless `cells` fills
+    /// whole rows of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of {}",
+            cells.len(),
+            width
+        );
+        return Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        };
+    }
+
+    /// Reads one cell per character and one row per line, reporting the first
+    /// character `cell` rejects with `expected` as the message.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (row, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], expected))?;
+                cells.push(value);
+            }
+
+            if row == 0 {
+                width = cells.len();
+                if width == 0 {
+                    break;
+                }
+            } else if cells.len() - start != width {
+                let message = format!("expected {} columns in every row", width);
+                return Err(ParseError::at(input, line, message));
+            }
+        }
+
+        if width == 0 {
+            return Err(ParseError::at_end(input, "empty grid"));
+        }
+        return Ok(Grid::from_vec(width, cells));
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        return point.row < self.height && point.col < self.width;
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if !self.contains(point) {
+            return None;
+        }
+        return self.cells.get(point.row * self.width + point.col);
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if !self.contains(point) {
+            return None;
+        }
+        return self.cells.get_mut(point.row * self.width + point.col);
+    }
+
+    /// The point one step from `point` in `direction`, if it is on the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        let (rows, cols) = direction.offset();
+        let next = Point::new(
+            point.row.checked_add_signed(rows)?,
+            point.col.checked_add_signed(cols)?,
+        );
+        return self.contains(next).then_some(next);
+    }
+
+    /// The up to four points sharing an edge with `point`.
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        return Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d));
+    }
+
+    /// The up to eight points sharing an edge or a corner with `point`.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        return Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d));
+    }
+
+    /// Every point from `point`, not included, to the edge in `direction`.
+    pub fn ray(&self, point: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        let mut current = point;
+        return core::iter::from_fn(move || {
+            current = self.step(current, direction)?;
+            return Some(current);
+        });
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        return &self.cells[row * self.width..(row + 1) * self.width];
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return self.cells.chunks(self.width);
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} out of bounds", col);
+        return self.cells.iter().skip(col).step_by(self.width);
+    }
+
+    /// Every point in row order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        return (0..self.cells.len()).map(move |i| Point::new(i / width, i % width));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        return self.points().zip(&self.cells);
+    }
+
+    /// The first point in row order whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        let i = self.cells.iter().position(predicate)?;
+        return Some(Point::new(i / self.width, i % self.width));
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        return Grid::from_vec(self.width, self.cells.iter().map(f).collect());
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        return Grid::from_vec(width, vec![value; width * height]);
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        return self
+            .get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point));
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        return self
+            .get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point));
+    }
+}
+
+/// Draws the grid a row to a line, each cell as it displays.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        return Grid::parse(input, "expected a digit", |c| c.to_digit(10));
+    }
+
+    #[test]
+    fn parse_reads_rows_in_order() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(6, grid[Point::new(1, 2)]);
+        assert_eq!(None, grid.get(Point::new(2, 0)));
+        assert_eq!("123\n456\n", grid.to_string());
+    }
+
+    #[test]
+    fn parse_reports_bad_cells_and_ragged_rows() {
+        let err = digits("123\n4x6\n").unwrap_err();
+        assert_eq!("line 2, column 2: expected a digit: `x`", err.to_string());
+        let err = digits("123\n45\n").unwrap_err();
+        assert_eq!(
+            "line 2, column 1: expected 3 columns in every row: `45`",
+            err.to_string()
+        );
+        let err = digits("\n").unwrap_err();
+        assert_eq!("line 2, column 1: empty grid", err.to_string());
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::new(3, 2, 0);
+        let corner: Vec<Point> = grid.neighbors(Point::new(0, 0)).collect();
+        assert_eq!(vec![Point::new(1, 0), Point::new(0, 1)], corner);
+        assert_eq!(5, grid.neighbors8(Point::new(1, 1)).count());
+    }
+
+    #[test]
+    fn ray_runs_to_the_edge() {
+        let grid = Grid::new(4, 4, 0);
+        let ray: Vec<Point> = grid.ray(Point::new(1, 1), Direction::DownRight).collect();
+        assert_eq!(vec![Point::new(2, 2), Point::new(3, 3)], ray);
+        assert_eq!(0, grid.ray(Point::new(0, 2), Direction::Up).count());
+    }
+}
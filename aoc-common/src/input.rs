@@ -0,0 +1,109 @@
+use std::{
+    borrow::Cow,
+    env,
+    fmt::{self, Display},
+    fs,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Part};
+
+/// Solves one part straight from a reader, for inputs too big to hold in
+/// memory at once.
+pub type Streamer = fn(&mut dyn BufRead, Part) -> Result<String, Error>;
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Embedded,
+}
+
+impl InputSource {
+    /// Uses `arg` if given, where `-` means stdin, then the path in the day's
+    /// `AOC_DAYNN_INPUT` environment variable, then the embedded input.
+    pub fn resolve(day: u8, arg: Option<&Path>) -> Self {
+        let arg = arg.map(PathBuf::from).or_else(|| {
+            env::var_os(input_var(day))
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        });
+
+        return match arg {
+            Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+            Some(path) => InputSource::Path(path),
+            None => InputSource::Embedded,
+        };
+    }
+
+    pub fn read(&self, embedded: &'static str) -> Result<Cow<'static, str>, Error> {
+        return match self {
+            InputSource::Path(path) => fs::read_to_string(path)
+                .map(Cow::Owned)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e).into()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("could not read stdin: {}", e))?;
+                Ok(Cow::Owned(input))
+            }
+            InputSource::Embedded => Ok(Cow::Borrowed(embedded)),
+        };
+    }
+
+    /// Opens the input for reading bit by bit instead of all at once.
+    pub fn open(&self, embedded: &'static str) -> Result<Box<dyn BufRead>, Error> {
+        return match self {
+            InputSource::Path(path) => {
+                let file = fs::File::open(path)
+                    .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::Embedded => Ok(Box::new(embedded.as_bytes())),
+        };
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Embedded => write!(f, "embedded input"),
+        };
+    }
+}
+
+/// The environment variable naming an input file for `day`, e.g. `AOC_DAY07_INPUT`.
+pub fn input_var(day: u8) -> String {
+    return format!("AOC_DAY{:02}_INPUT", day);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn input_source_prefers_argument() {
+        let source = InputSource::resolve(99, Some(Path::new("-")));
+        assert_eq!(InputSource::Stdin, source);
+        let source = InputSource::resolve(99, Some(Path::new("input.txt")));
+        assert_eq!(InputSource::Path(PathBuf::from("input.txt")), source);
+        assert_eq!(InputSource::Embedded, InputSource::resolve(99, None));
+    }
+
+    #[test]
+    fn input_source_names_missing_path() {
+        let source = InputSource::Path(PathBuf::from("missing/input.txt"));
+        let err = source.read("").unwrap_err().to_string();
+        assert!(
+            err.starts_with("could not read missing/input.txt: "),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,296 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+#[cfg(feature = "std")]
+mod answers;
+#[cfg(feature = "std")]
+mod generate;
+mod grid;
+#[cfg(feature = "std")]
+mod input;
+pub mod parse;
+mod registry;
+
+// Lets the code `#[aoc]` expands to name this crate from inside it too.
+extern crate self as aoc_common;
+
+#[cfg(feature = "std")]
+pub use answers::{check_known_inputs, known_inputs, KnownInput};
+#[cfg(feature = "std")]
+pub use generate::{Answers, Generator, Rng};
+pub use grid::{Direction, Grid, Point};
+#[cfg(feature = "std")]
+pub use input::{input_var, InputSource, Streamer};
+pub use registry::{variants_of, IntoAnswer, Registered, REGISTERED};
+
+pub use aoc_macros::aoc;
+#[doc(hidden)]
+pub use linkme;
+
+pub type Error = Box<dyn core::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Part::One => "part_one",
+            Part::Two => "part_two",
+        };
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        };
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        return match part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        return match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, got {}", value).into()),
+        };
+    }
+}
+
+/// Malformed puzzle input, pointing at the offending text. `line` and `column`
+/// are 1-based and `column` counts characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Reports `span`, which must be a slice of `input`, at its position in
+    /// `input`.
+    pub fn at(input: &str, span: &str, message: impl Display) -> Self {
+        let offset = (span.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(offset <= input.len(), "span is not part of the input");
+        let before = &input[..offset.min(input.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        return ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: span.to_string(),
+            message: message.to_string(),
+        };
+    }
+
+    /// Reports something missing from the end of `input`.
+    pub fn at_end(input: &str, message: impl Display) -> Self {
+        return ParseError::at(input, &input[input.len()..], message);
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, ": `{}`", self.text)?;
+        }
+        return Ok(());
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+pub type Solver = fn(&str) -> Result<String, Error>;
+
+/// Draws how a day reaches its answers as text, e.g. the path it found.
+pub type Visualizer = fn(&str) -> Result<String, Error>;
+
+/// One way of solving a part, taking the raw puzzle input.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub part: Part,
+    pub name: &'static str,
+    pub solve: Solver,
+}
+
+impl Variant {
+    pub const fn new(part: Part, name: &'static str, solve: Solver) -> Self {
+        return Variant { part, name, solve };
+    }
+}
+
+/// A day's puzzle, split into a parse step shared by both parts and the two
+/// solve steps, so each can be timed on its own.
+pub trait Solution {
+    const DAY: u8;
+    /// The puzzle input compiled into the crate, used when no other is given.
+    const INPUT: &'static str;
+    /// Days whose input can be solved a line or a byte at a time say how here.
+    #[cfg(feature = "std")]
+    const STREAM: Option<Streamer> = None;
+    /// Builds inputs of any size, for stress tests and benchmarks.
+    #[cfg(feature = "std")]
+    const GENERATE: Option<Generator> = None;
+    /// Days with something worth seeing draw it here, for `aoc tui`.
+    const VISUALIZE: Option<Visualizer> = None;
+    /// Bump whenever a change could alter an answer, so the runner stops
+    /// trusting answers it cached from older versions.
+    const VERSION: u32 = 1;
+
+    type Input<'a>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error>;
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error>;
+
+    /// This impl as the `iterator` variant of both parts, followed by every
+    /// function registered for this day with `#[aoc]`.
+    fn variants() -> Vec<Variant>
+    where
+        Self: Sized,
+    {
+        let solution = [
+            Variant::new(Part::One, "iterator", |i| solve::<Self>(i, Part::One)),
+            Variant::new(Part::Two, "iterator", |i| solve::<Self>(i, Part::Two)),
+        ];
+        return variants_of(Self::DAY, &solution);
+    }
+}
+
+/// A parsed input that can be solved for either part without parsing again.
+pub type Prepared<'a> = Box<dyn Fn(Part) -> Result<String, Error> + 'a>;
+
+/// Parses `input` once so that benchmarks can time solving on its own.
+pub fn prepare<'a, S: Solution + 'static>(input: &'a str) -> Result<Prepared<'a>, Error> {
+    let parsed = S::parse(input)?;
+    return Ok(Box::new(move |part| {
+        return match part {
+            Part::One => S::part_one(&parsed).map(|a| a.to_string()),
+            Part::Two => S::part_two(&parsed).map(|a| a.to_string()),
+        };
+    }));
+}
+
+/// Parses `input` and solves a single part.
+pub fn solve<S: Solution>(input: &str, part: Part) -> Result<String, Error> {
+    let parsed = S::parse(input)?;
+    return match part {
+        Part::One => S::part_one(&parsed).map(|a| a.to_string()),
+        Part::Two => S::part_two(&parsed).map(|a| a.to_string()),
+    };
+}
+
+/// Runs every variant of `part` against `input` and returns the answer they
+/// all agree on, or an error listing each variant's answer if they do not.
+pub fn cross_check(variants: &[Variant], part: Part, input: &str) -> Result<String, Error> {
+    let answers: Vec<(&str, Result<String, Error>)> = variants
+        .iter()
+        .filter(|v| v.part == part)
+        .map(|v| (v.name, (v.solve)(input)))
+        .collect();
+
+    let Some((_, Ok(first))) = answers.first() else {
+        return match answers.first() {
+            Some((name, Err(e))) => Err(format!("part {} ({}) failed: {}", part, name, e).into()),
+            _ => Err(format!("no variants registered for part {}", part).into()),
+        };
+    };
+
+    if answers
+        .iter()
+        .all(|(_, answer)| answer.as_ref().is_ok_and(|a| a == first))
+    {
+        return Ok(first.clone());
+    }
+
+    let listing: Vec<String> = answers
+        .iter()
+        .map(|(name, answer)| match answer {
+            Ok(answer) => format!("{} = {:?}", name, answer),
+            Err(e) => format!("{} failed: {}", name, e),
+        })
+        .collect();
+    return Err(format!("part {} variants disagree: {}", part, listing.join(", ")).into());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const VARIANTS: &[Variant] = &[
+        Variant::new(Part::One, "length", |i| Ok(i.len().to_string())),
+        Variant::new(Part::One, "chars", |i| Ok(i.chars().count().to_string())),
+        Variant::new(Part::Two, "lines", |i| Ok(i.lines().count().to_string())),
+    ];
+
+    #[test]
+    fn parse_error_locates_span() {
+        let input = "1000\n2000\n\nab3c\n";
+        let err = ParseError::at(input, &input[13..15], "invalid digit");
+        assert_eq!((4, 3, "3c"), (err.line, err.column, err.text.as_str()));
+        assert_eq!("line 4, column 3: invalid digit: `3c`", err.to_string());
+    }
+
+    #[test]
+    fn parse_error_locates_end_of_input() {
+        let err = ParseError::at_end("abc\nde", "missing blank line");
+        assert_eq!("line 2, column 3: missing blank line", err.to_string());
+    }
+
+    #[test]
+    fn cross_check_returns_agreed_answer() {
+        assert_eq!("3", cross_check(VARIANTS, Part::One, "abc").unwrap());
+        assert_eq!("1", cross_check(VARIANTS, Part::Two, "abc").unwrap());
+    }
+
+    #[test]
+    fn cross_check_reports_disagreement() {
+        let err = cross_check(VARIANTS, Part::One, "äbc").unwrap_err();
+        assert_eq!(
+            "part 1 variants disagree: length = \"4\", chars = \"3\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn cross_check_requires_a_variant() {
+        let variants = &VARIANTS[..2];
+        assert!(cross_check(variants, Part::Two, "abc").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Helpers for splitting puzzle input into borrowed pieces. Every function
+//! takes the whole `input` alongside the piece it works on, so that errors
+//! point at the offending text with [`ParseError::at`].
+
+use alloc::format;
+use core::{fmt::Display, str::FromStr};
+
+use crate::ParseError;
+
+/// Parses `text` with its `FromStr` impl.
+pub fn number<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    return text.parse().map_err(|e| ParseError::at(input, text, e));
+}
+
+/// Reads `text` as an unsigned decimal number straight from its bytes, which
+/// is quicker than `str::parse` for the short numbers puzzles are made of.
+pub fn unsigned<T: TryFrom<u64>>(input: &str, text: &str) -> Result<T, ParseError> {
+    if text.is_empty() {
+        return Err(ParseError::at(input, text, "expected a number"));
+    }
+
+    let mut value: u64 = 0;
+    for (i, byte) in text.bytes().enumerate() {
+        if !byte.is_ascii_digit() {
+            let c = text[i..].chars().next().unwrap_or_default();
+            let span = &text[i..i + c.len_utf8()];
+            return Err(ParseError::at(input, span, "expected a digit"));
+        }
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((byte - b'0') as u64))
+            .ok_or_else(|| ParseError::at(input, text, "number too large"))?;
+    }
+
+    return T::try_from(value).map_err(|_| ParseError::at(input, text, "number too large"));
+}
+
+/// Splits `record` on any of `separators` into exactly `N` fields.
+pub fn fields<'a, const N: usize>(
+    input: &str,
+    record: &'a str,
+    separators: &[char],
+) -> Result<[&'a str; N], ParseError> {
+    let mut fields = [""; N];
+    let mut split = record.split(separators);
+    for field in fields.iter_mut() {
+        *field = split
+            .next()
+            .ok_or_else(|| ParseError::at(input, record, format!("expected {} fields", N)))?;
+    }
+    if let Some(extra) = split.next() {
+        let rest = &record[extra.as_ptr() as usize - record.as_ptr() as usize..];
+        return Err(ParseError::at(
+            input,
+            rest,
+            format!("expected {} fields", N),
+        ));
+    }
+
+    return Ok(fields);
+}
+
+/// The value of a `label value` line, such as `Test: divisible by 23`, with
+/// surrounding whitespace removed.
+pub fn labelled<'a>(input: &str, line: &'a str, label: &str) -> Result<&'a str, ParseError> {
+    return line
+        .trim_start()
+        .strip_prefix(label)
+        .map(str::trim)
+        .ok_or_else(|| ParseError::at(input, line, format!("expected `{}`", label)));
+}
+
+/// The groups of lines in `text` separated by blank lines, each without its
+/// trailing newline. Runs of blank lines count as one separator.
+pub fn blocks(text: &str) -> impl Iterator<Item = &str> {
+    let mut lines = text.lines().peekable();
+    return core::iter::from_fn(move || {
+        while lines.next_if(|l| l.is_empty()).is_some() {}
+        let first = lines.next()?;
+        let mut last = first;
+        while let Some(line) = lines.next_if(|l| !l.is_empty()) {
+            last = line;
+        }
+
+        let start = first.as_ptr() as usize - text.as_ptr() as usize;
+        let end = last.as_ptr() as usize - text.as_ptr() as usize + last.len();
+        return Some(&text[start..end]);
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn unsigned_reads_digits() {
+        assert_eq!(Ok(4096u32), unsigned("4096", "4096"));
+        let err = unsigned::<u32>("12\n3x4\n", &"12\n3x4\n"[3..6]).unwrap_err();
+        assert_eq!("line 2, column 2: expected a digit: `x`", err.to_string());
+        let err = unsigned::<u8>("256", "256").unwrap_err();
+        assert_eq!("line 1, column 1: number too large: `256`", err.to_string());
+    }
+
+    #[test]
+    fn fields_requires_exact_count() {
+        assert_eq!(
+            Ok(["2", "4", "6", "8"]),
+            fields("2-4,6-8", "2-4,6-8", &[',', '-'])
+        );
+        let err = fields::<2>("R 4 2", "R 4 2", &[' ']).unwrap_err();
+        assert_eq!("line 1, column 5: expected 2 fields: `2`", err.to_string());
+        let err = fields::<2>("R", "R", &[' ']).unwrap_err();
+        assert_eq!("line 1, column 1: expected 2 fields: `R`", err.to_string());
+    }
+
+    #[test]
+    fn labelled_strips_label() {
+        let line = "  Test: divisible by 23";
+        assert_eq!(Ok("23"), labelled(line, line, "Test: divisible by"));
+        assert!(labelled(line, line, "Operation:").is_err());
+    }
+
+    #[test]
+    fn blocks_borrow_from_input() {
+        let input = "1\n2\n\n\n3\n\n4";
+        let found: Vec<&str> = blocks(input).collect();
+        assert_eq!(vec!["1\n2", "3", "4"], found);
+        assert_eq!(0, blocks("\n\n").count());
+    }
+}
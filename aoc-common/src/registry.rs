@@ -0,0 +1,130 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use linkme::distributed_slice;
+
+use crate::{Error, Variant};
+
+/// A variant registered with `#[aoc(...)]`.
+pub struct Registered {
+    pub day: u8,
+    pub variant: Variant,
+}
+
+/// Every `#[aoc(...)]` function in the crates linked into this binary.
+#[distributed_slice]
+pub static REGISTERED: [Registered];
+
+/// The variants `listed` for `day` followed by those registered for it, part
+/// one first, registered ones in name order.
+///
+/// Panics if two of them share a part and a name, since only one of the two
+/// would ever be run by name.
+pub fn variants_of(day: u8, listed: &[Variant]) -> Vec<Variant> {
+    let mut registered: Vec<Variant> = REGISTERED
+        .iter()
+        .filter(|r| r.day == day)
+        .map(|r| r.variant)
+        .collect();
+    registered.sort_by_key(|v| (v.part, v.name));
+
+    let mut variants: Vec<Variant> = listed.iter().copied().chain(registered).collect();
+    variants.sort_by_key(|v| v.part);
+
+    for (i, variant) in variants.iter().enumerate() {
+        if variants[..i]
+            .iter()
+            .any(|v| v.part == variant.part && v.name == variant.name)
+        {
+            panic!(
+                "day {} part {} has more than one variant named `{}`",
+                day, variant.part, variant.name
+            );
+        }
+    }
+    return variants;
+}
+
+/// What an `#[aoc(...)]` function may return, turned into the answer text.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<String, Error>;
+}
+
+macro_rules! into_answer {
+    ($($t:ty),*) => {
+        $(
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Result<String, Error> {
+                    return Ok(self.to_string());
+                }
+            }
+        )*
+    };
+}
+
+into_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, &str);
+
+impl<T: Display> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Result<String, Error> {
+        return self
+            .map(|answer| answer.to_string())
+            .ok_or_else(|| "found no answer".into());
+    }
+}
+
+impl<T: Display, E: Into<Error>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Error> {
+        return self.map(|answer| answer.to_string()).map_err(Into::into);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use aoc_macros::aoc;
+
+    use super::*;
+    use crate::{cross_check, Part};
+
+    #[aoc(day = 25, part = 2, variant = "lines")]
+    fn count_lines(input: &str) -> usize {
+        return input.lines().count();
+    }
+
+    #[aoc(day = 25, part = 1)]
+    fn first_digit(input: &str) -> Option<u32> {
+        return input.chars().find_map(|c| c.to_digit(10));
+    }
+
+    #[test]
+    fn registered_variants_join_listed_ones() {
+        let listed = [Variant::new(Part::One, "iterator", |i| {
+            Ok(i[..1].to_string())
+        })];
+        let variants = variants_of(25, &listed);
+
+        let names: Vec<(Part, &str)> = variants.iter().map(|v| (v.part, v.name)).collect();
+        assert_eq!(
+            vec![
+                (Part::One, "iterator"),
+                (Part::One, "first_digit"),
+                (Part::Two, "lines")
+            ],
+            names
+        );
+        assert_eq!("7", cross_check(&variants, Part::One, "7a\nb").unwrap());
+        assert_eq!("2", cross_check(&variants, Part::Two, "7a\nb").unwrap());
+        assert!(cross_check(&variants, Part::One, "a").is_err());
+        assert!(variants_of(24, &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "day 25 part 2 has more than one variant named `lines`")]
+    fn variants_of_rejects_duplicate_names() {
+        let listed = [Variant::new(Part::Two, "lines", |i| Ok(i.to_string()))];
+        variants_of(25, &listed);
+    }
+}
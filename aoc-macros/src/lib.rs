@@ -0,0 +1,93 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, parse_macro_input, Error, ItemFn, LitInt, LitStr};
+
+#[derive(Default)]
+struct Args {
+    day: Option<u8>,
+    part: Option<u8>,
+    variant: Option<String>,
+}
+
+impl Args {
+    fn parse(&mut self, meta: ParseNestedMeta) -> Result<(), Error> {
+        if meta.path.is_ident("day") {
+            let day: LitInt = meta.value()?.parse()?;
+            let number = day.base10_parse()?;
+            if !(1..=25).contains(&number) {
+                return Err(Error::new(day.span(), "day must be from 1 to 25"));
+            }
+            self.day = Some(number);
+        } else if meta.path.is_ident("part") {
+            let part: LitInt = meta.value()?.parse()?;
+            let number = part.base10_parse()?;
+            if !(1..=2).contains(&number) {
+                return Err(Error::new(part.span(), "part must be 1 or 2"));
+            }
+            self.part = Some(number);
+        } else if meta.path.is_ident("variant") {
+            let variant: LitStr = meta.value()?.parse()?;
+            self.variant = Some(variant.value());
+        } else {
+            return Err(meta.error("expected `day`, `part` or `variant`"));
+        }
+        return Ok(());
+    }
+}
+
+/// Registers a function taking the raw puzzle input as a variant of a day's
+/// part, next to the day's `Solution` impl:
+///
+/// ```ignore
+/// #[aoc(day = 6, part = 2, variant = "bitwise")]
+/// pub fn part_two_bitwise(input: &str) -> Option<usize> { ... }
+/// ```
+///
+/// `variant` defaults to the function's name. The function may return
+/// anything `aoc_common::IntoAnswer` covers.
+#[proc_macro_attribute]
+pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attr with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    return match expand(args, function) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    };
+}
+
+fn expand(args: Args, function: ItemFn) -> Result<proc_macro2::TokenStream, Error> {
+    let missing = |name: &str| Error::new(Span::call_site(), format!("missing `{}`", name));
+    let day = args.day.ok_or_else(|| missing("day"))?;
+    let part = match args.part.ok_or_else(|| missing("part"))? {
+        1 => quote!(::aoc_common::Part::One),
+        _ => quote!(::aoc_common::Part::Two),
+    };
+
+    let signature = &function.sig;
+    if signature.inputs.len() != 1 || !signature.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            signature,
+            "expected a function taking only the puzzle input, as `&str`",
+        ));
+    }
+    let name = &signature.ident;
+    let variant = args.variant.unwrap_or_else(|| name.to_string());
+    let registered = format_ident!("AOC_VARIANT_{}", name.to_string().to_uppercase());
+
+    return Ok(quote! {
+        #function
+
+        #[::aoc_common::linkme::distributed_slice(::aoc_common::REGISTERED)]
+        #[linkme(crate = ::aoc_common::linkme)]
+        static #registered: ::aoc_common::Registered = ::aoc_common::Registered {
+            day: #day,
+            variant: ::aoc_common::Variant::new(#part, #variant, |input| {
+                ::aoc_common::IntoAnswer::into_answer(#name(input))
+            }),
+        };
+    });
+}
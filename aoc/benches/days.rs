@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+use aoc::{
+    alloc::measure,
+    days::{Day, DAYS},
+};
+use aoc_common::{InputSource, Part};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+};
+
+// A single solve slower than this gets fewer samples so the whole run stays short.
+const SLOW: Duration = Duration::from_millis(20);
+
+// With the `count-alloc` feature, also prints what one run of `f` allocates,
+// since criterion itself only records time.
+fn bench<T>(
+    group: &mut BenchmarkGroup<WallTime>,
+    id: BenchmarkId,
+    name: String,
+    f: impl Fn() -> T,
+) {
+    let mut printed = false;
+    group.bench_function(id, |b| {
+        if !printed {
+            if let Some(alloc) = measure(&f).alloc {
+                println!("{}: {}", name, alloc);
+            }
+            printed = true;
+        }
+        b.iter(&f)
+    });
+}
+
+fn bench_day(c: &mut Criterion, day: &Day) {
+    let input = InputSource::resolve(day.number, None)
+        .read(day.input)
+        .expect("input should be readable");
+    let prepared = match (day.prepare)(&input) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            eprintln!("day{:02}: skipped, parse failed: {}", day.number, e);
+            return;
+        }
+    };
+
+    let mut slowest = Duration::ZERO;
+    let mut solvable = Vec::new();
+    for part in Part::ALL {
+        let start = Instant::now();
+        match prepared(part) {
+            Ok(_) => solvable.push(part),
+            Err(e) => eprintln!("day{:02}/{}: skipped: {}", day.number, part.name(), e),
+        }
+        slowest = slowest.max(start.elapsed());
+    }
+
+    let group_name = format!("day{:02}", day.number);
+    let mut group = c.benchmark_group(&group_name);
+    if slowest > SLOW {
+        group.sample_size(10);
+    }
+
+    bench(
+        &mut group,
+        BenchmarkId::from_parameter("parse"),
+        format!("{}/parse", group_name),
+        || (day.prepare)(&input),
+    );
+    for &part in &solvable {
+        bench(
+            &mut group,
+            BenchmarkId::new(part.name(), "solve"),
+            format!("{}/{}/solve", group_name, part.name()),
+            || prepared(part),
+        );
+        for variant in day.variants_for(part) {
+            bench(
+                &mut group,
+                BenchmarkId::new(part.name(), variant.name),
+                format!("{}/{}/{}", group_name, part.name(), variant.name),
+                || (variant.solve)(&input),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn benchmark(c: &mut Criterion) {
+    for day in DAYS {
+        bench_day(c, day);
+    }
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,172 @@
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// Heap use while running one measured closure. `peak` is the most memory it
+/// held at once on top of what was already allocated when it started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "{} allocs, {} allocated, peak {}",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        );
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    return match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    };
+}
+
+pub struct Measured<T> {
+    pub value: T,
+    pub elapsed: Duration,
+    /// `None` unless built with the `count-alloc` feature.
+    pub alloc: Option<AllocStats>,
+}
+
+/// Times `f` and, with the `count-alloc` feature, counts its allocations.
+/// Counts are process-wide, so nothing else should allocate meanwhile.
+pub fn measure<T>(f: impl FnOnce() -> T) -> Measured<T> {
+    #[cfg(feature = "count-alloc")]
+    let before = counting::start();
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+
+    return Measured {
+        value,
+        elapsed,
+        #[cfg(feature = "count-alloc")]
+        alloc: Some(counting::stop(before)),
+        #[cfg(not(feature = "count-alloc"))]
+        alloc: None,
+    };
+}
+
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::AllocStats;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static BYTES: AtomicUsize = AtomicUsize::new(0);
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counting;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    fn record(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                record(layout.size());
+            }
+            return ptr;
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                record(layout.size());
+            }
+            return ptr;
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+                record(new_size);
+            }
+            return new;
+        }
+    }
+
+    /// Snapshot taken before a measurement, with the peak reset to what is
+    /// allocated right now.
+    pub struct Before {
+        allocations: usize,
+        bytes: usize,
+        current: usize,
+    }
+
+    pub fn start() -> Before {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        return Before {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            current,
+        };
+    }
+
+    pub fn stop(before: Before) -> AllocStats {
+        return AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - before.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - before.bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(before.current),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_bytes_picks_unit() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("2.0 MiB", format_bytes(2 * 1_048_576));
+    }
+
+    #[cfg(feature = "count-alloc")]
+    #[test]
+    fn measure_counts_allocations() {
+        let measured = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(1024);
+            v.push(1);
+            drop(v);
+            return Box::new(7u8);
+        });
+        let alloc = measured.alloc.unwrap();
+
+        assert!(alloc.allocations >= 2, "{:?}", alloc);
+        assert!(alloc.bytes > 8 * 1024, "{:?}", alloc);
+        assert!(alloc.peak >= 8 * 1024, "{:?}", alloc);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_common::Error;
+
+use crate::days::{target_dir, workspace_dir, DAYS};
+
+/// The median time of each benchmark from `cargo bench -p aoc`, keyed by its
+/// criterion id, e.g. `day06/part_one/bitwise`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    pub benches: BTreeMap<String, Duration>,
+}
+
+/// A benchmark present in both the baseline and the latest run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub id: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Change {
+    /// How much slower the latest run is, in percent; negative when faster.
+    pub fn percent(&self) -> f64 {
+        return (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0;
+    }
+}
+
+/// Where criterion writes its results, following `CRITERION_HOME` and
+/// `CARGO_TARGET_DIR` the way criterion itself does.
+pub fn criterion_dir() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    return target_dir().join("criterion");
+}
+
+/// Saved baselines live outside `target/` so `cargo clean` keeps them.
+pub fn baseline_path(name: &str) -> PathBuf {
+    return workspace_dir()
+        .join("baselines")
+        .join(format!("{}.toml", name));
+}
+
+impl Baseline {
+    /// Collects the latest result of every `dayNN` benchmark under `dir`.
+    pub fn from_criterion(dir: &Path) -> Result<Self, Error> {
+        let mut baseline = Baseline::default();
+        for day in DAYS {
+            let group = dir.join(format!("day{:02}", day.number));
+            if group.is_dir() {
+                collect(&group, &mut baseline.benches)?;
+            }
+        }
+
+        if baseline.benches.is_empty() {
+            return Err(format!(
+                "no benchmark results in {}, run `cargo bench -p aoc` first",
+                dir.display()
+            )
+            .into());
+        }
+        return Ok(baseline);
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| format!("invalid {}: {}", path.display(), e))?;
+
+        let mut baseline = Baseline::default();
+        for (id, value) in table {
+            let nanos = match value {
+                toml::Value::Float(n) => n,
+                toml::Value::Integer(n) => n as f64,
+                _ => return Err(format!("{}: {} must be a number", path.display(), id).into()),
+            };
+            baseline.benches.insert(id, from_nanos(nanos));
+        }
+        return Ok(baseline);
+    }
+
+    /// Writes the baseline as a table of whole nanoseconds per benchmark.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let table: toml::Table = self
+            .benches
+            .iter()
+            .map(|(id, time)| (id.clone(), toml::Value::Integer(time.as_nanos() as i64)))
+            .collect();
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(&table)?)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        return Ok(());
+    }
+
+    /// Pairs every benchmark in `current` with its time in this baseline.
+    pub fn compare(&self, current: &Baseline) -> Vec<Change> {
+        return current
+            .benches
+            .iter()
+            .filter_map(|(id, &time)| {
+                let &baseline = self.benches.get(id)?;
+                return Some(Change {
+                    id: id.clone(),
+                    baseline,
+                    current: time,
+                });
+            })
+            .collect();
+    }
+}
+
+// Criterion keeps each benchmark's latest run in `<id>/new/`, with the id
+// itself in `benchmark.json` since directory names are sanitised.
+fn collect(dir: &Path, benches: &mut BTreeMap<String, Duration>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() || path.ends_with("report") {
+            continue;
+        }
+        if !path.ends_with("new") {
+            collect(&path, benches)?;
+            continue;
+        }
+
+        let benchmark = read_json(&path.join("benchmark.json"))?;
+        let estimates = read_json(&path.join("estimates.json"))?;
+        let id = benchmark["full_id"].as_str();
+        let median = estimates["median"]["point_estimate"].as_f64();
+        let (Some(id), Some(median)) = (id, median) else {
+            return Err(format!("unexpected criterion output in {}", path.display()).into());
+        };
+        benches.insert(id.to_string(), from_nanos(median));
+    }
+
+    return Ok(());
+}
+
+fn from_nanos(nanos: f64) -> Duration {
+    return Duration::from_nanos(nanos.round() as u64);
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value, Error> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    return serde_json::from_str(&text)
+        .map_err(|e| format!("invalid {}: {}", path.display(), e).into());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn baseline(benches: &[(&str, u64)]) -> Baseline {
+        return Baseline {
+            benches: benches
+                .iter()
+                .map(|&(id, nanos)| (id.to_string(), Duration::from_nanos(nanos)))
+                .collect(),
+        };
+    }
+
+    // A directory under the temp dir, removed when the test ends.
+    struct Scratch {
+        dir: PathBuf,
+    }
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            return Scratch { dir };
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn write_result(dir: &Path, id: &str, median: f64) {
+        let new = dir.join(id).join("new");
+        fs::create_dir_all(&new).unwrap();
+        fs::write(
+            new.join("benchmark.json"),
+            format!("{{\"full_id\":\"{}\"}}", id),
+        )
+        .unwrap();
+        fs::write(
+            new.join("estimates.json"),
+            format!("{{\"median\":{{\"point_estimate\":{}}}}}", median),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn from_criterion_reads_latest_results() {
+        let scratch = Scratch::new("criterion");
+        let dir = &scratch.dir;
+        write_result(dir, "day06/part_one/bitwise", 2500.0);
+        write_result(dir, "day06/parse", 100.0);
+        write_result(dir, "day_six/part_one", 1.0);
+        fs::create_dir_all(dir.join("day06/report")).unwrap();
+
+        let read = Baseline::from_criterion(dir).unwrap();
+        assert_eq!(
+            baseline(&[("day06/parse", 100), ("day06/part_one/bitwise", 2500)]),
+            read
+        );
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let scratch = Scratch::new("baseline");
+        let path = scratch.dir.join("before.toml");
+        let saved = baseline(&[("day01/parse", 1234), ("day11/part_two/solve", 184_000_000)]);
+        saved.save(&path).unwrap();
+        assert_eq!(saved, Baseline::load(&path).unwrap());
+    }
+
+    #[test]
+    fn compare_pairs_shared_benchmarks() {
+        let before = baseline(&[("day01/parse", 1000), ("day02/parse", 1000)]);
+        let after = baseline(&[("day01/parse", 1100), ("day03/parse", 10)]);
+        let changes = before.compare(&after);
+
+        assert_eq!(1, changes.len());
+        assert_eq!("day01/parse", changes[0].id);
+        assert!((changes[0].percent() - 10.0).abs() < 1e-9);
+    }
+}
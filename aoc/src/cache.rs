@@ -0,0 +1,118 @@
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{Error, Part};
+use sha2::{Digest, Sha256};
+
+use crate::days::{target_dir, Day};
+
+/// What a cached answer was computed from: the day, its solution's version
+/// stamp, the part and the input's SHA-256.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    pub day: u8,
+    pub version: u32,
+    pub part: Part,
+    pub input: String,
+}
+
+impl Key {
+    pub fn new(day: &Day, part: Part, input: &str) -> Self {
+        let mut hash = String::with_capacity(64);
+        for byte in Sha256::digest(input.as_bytes()) {
+            write!(hash, "{:02x}", byte).unwrap();
+        }
+
+        return Key {
+            day: day.number,
+            version: day.version,
+            part,
+            input: hash,
+        };
+    }
+}
+
+/// Answers of the days' `Solution` impls, one file per key.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        return Cache { dir: dir.into() };
+    }
+
+    /// Lives in the target directory, so `cargo clean` empties it.
+    pub fn open() -> Self {
+        return Cache::new(target_dir().join("aoc-cache"));
+    }
+
+    pub fn dir(&self) -> &Path {
+        return &self.dir;
+    }
+
+    fn path(&self, key: &Key) -> PathBuf {
+        return self.dir.join(format!("day{:02}", key.day)).join(format!(
+            "{}-v{}-{}.txt",
+            key.input,
+            key.version,
+            key.part.name()
+        ));
+    }
+
+    pub fn get(&self, key: &Key) -> Result<Option<String>, Error> {
+        let path = self.path(key);
+        return match fs::read_to_string(&path) {
+            Ok(answer) => Ok(Some(answer)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("could not read {}: {}", path.display(), e).into()),
+        };
+    }
+
+    pub fn put(&self, key: &Key, answer: &str) -> Result<(), Error> {
+        let path = self.path(key);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        return fs::write(&path, answer)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e).into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn answers_are_keyed_by_input_version_and_part() {
+        let cache = Cache::new(env::temp_dir().join(format!("aoc-cache-{}", std::process::id())));
+        let day = days::find(11).unwrap();
+        let key = Key::new(day, Part::Two, "Monkey 0:\n");
+
+        assert_eq!(None, cache.get(&key).unwrap());
+        cache.put(&key, "2713310158").unwrap();
+        assert_eq!(Some("2713310158".to_string()), cache.get(&key).unwrap());
+
+        let newer = Key {
+            version: key.version + 1,
+            ..key.clone()
+        };
+        assert_eq!(None, cache.get(&newer).unwrap());
+        assert_eq!(
+            None,
+            cache.get(&Key::new(day, Part::One, "Monkey 0:\n")).unwrap()
+        );
+        assert_eq!(
+            None,
+            cache.get(&Key::new(day, Part::Two, "Monkey 1:\n")).unwrap()
+        );
+
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+}
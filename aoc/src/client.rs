@@ -0,0 +1,267 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::Error;
+use ureq::{http::Response, Agent, Body};
+
+use crate::days::{target_dir, workspace_dir};
+
+pub const YEAR: u16 = 2022;
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/ryanseipp/aoc2022 runner";
+/// Shortest gap between two requests to the site, kept across runs.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Holds `session = "..."`, the value of the site's `session` cookie. It is
+/// ignored by git.
+pub fn config_path() -> PathBuf {
+    return workspace_dir().join(".aoc.toml");
+}
+
+/// The session token from `env`, the value of `$AOC_SESSION`, or else from
+/// the config file at `config`.
+pub fn session_token(env: Option<String>, config: &Path) -> Result<String, Error> {
+    if let Some(token) = env.filter(|t| !t.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+
+    let missing = format!(
+        "no session token: set $AOC_SESSION or `session` in {}",
+        config.display()
+    );
+    let Ok(text) = fs::read_to_string(config) else {
+        return Err(missing.into());
+    };
+    let table: toml::Table = text
+        .parse()
+        .map_err(|e| format!("invalid {}: {}", config.display(), e))?;
+    return match table.get("session").and_then(|s| s.as_str()) {
+        Some(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(missing.into()),
+    };
+}
+
+/// Talks to the Advent of Code site as a logged in user, never more often than
+/// its minimum interval.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    /// Holds the time of the last request, so the gap holds between runs too.
+    stamp: PathBuf,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>, stamp: PathBuf) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        return Client {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            min_interval: MIN_INTERVAL,
+            stamp,
+        };
+    }
+
+    /// Uses `$AOC_URL` if set, for testing against another server.
+    pub fn from_env() -> Result<Self, Error> {
+        let session = session_token(env::var("AOC_SESSION").ok(), &config_path())?;
+        let base_url = env::var("AOC_URL").unwrap_or_else(|_| BASE_URL.to_string());
+        let stamp = target_dir().join("aoc-cache").join("last-request");
+        return Ok(Client::new(base_url, session, stamp));
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        return self;
+    }
+
+    // Sleeps until the minimum interval has passed since the last request,
+    // then records this one.
+    fn wait_turn(&self) -> Result<(), Error> {
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+        let last = fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(wait) = last.and_then(|last| (last + self.min_interval).checked_sub(now())) {
+            thread::sleep(wait.min(self.min_interval));
+        }
+
+        if let Some(dir) = self.stamp.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.stamp, now().as_millis().to_string())?;
+        return Ok(());
+    }
+
+    fn url(&self, path: &str) -> String {
+        return format!("{}{}", self.base_url, path);
+    }
+
+    fn cookie(&self) -> String {
+        return format!("session={}", self.session);
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, Error> {
+        self.wait_turn()?;
+        let response = self
+            .agent
+            .get(self.url(path))
+            .header("Cookie", self.cookie())
+            .call()?;
+        return body(path, response);
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+        self.wait_turn()?;
+        let response = self
+            .agent
+            .post(self.url(path))
+            .header("Cookie", self.cookie())
+            .send_form(form.iter().copied())?;
+        return body(path, response);
+    }
+}
+
+fn body(path: &str, mut response: Response<Body>) -> Result<String, Error> {
+    return match response.status().as_u16() {
+        200 => Ok(response.body_mut().read_to_string()?),
+        400 => Err(format!("{}: the site rejected the session token", path).into()),
+        404 => Err(format!("{}: not found, the puzzle may not be unlocked yet", path).into()),
+        status => Err(format!("{}: the site answered with status {}", path, status).into()),
+    };
+}
+
+/// A stand-in for the site on localhost that answers requests with canned
+/// responses, in order, and hands back what it was sent.
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use tiny_http::{Header, Response, Server};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    pub struct Mock {
+        pub url: String,
+        pub requests: Receiver<Request>,
+    }
+
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> Mock {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let mut request = server.recv().unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+                let mut sent = String::new();
+                request.as_reader().read_to_string(&mut sent).unwrap();
+                sender
+                    .send(Request {
+                        method: request.method().to_string(),
+                        path: request.url().to_string(),
+                        cookie,
+                        body: sent,
+                    })
+                    .unwrap();
+
+                let html = Header::from_bytes("Content-Type", "text/html").unwrap();
+                let response = Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(html);
+                request.respond(response).unwrap();
+            }
+        });
+
+        return Mock { url, requests };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn stamp(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        return path;
+    }
+
+    #[test]
+    fn session_token_prefers_environment() {
+        let config = env::temp_dir().join(format!("aoc-config-{}.toml", std::process::id()));
+        fs::write(&config, "session = \"from-file\"\n").unwrap();
+
+        assert_eq!(
+            "from-env",
+            session_token(Some("from-env\n".to_string()), &config).unwrap()
+        );
+        assert_eq!("from-file", session_token(None, &config).unwrap());
+        assert!(session_token(Some(" ".to_string()), Path::new("/nonexistent")).is_err());
+
+        fs::remove_file(&config).unwrap();
+    }
+
+    #[test]
+    fn get_sends_session_and_reports_status() {
+        let server = mock::serve(vec![(200, "1000\n2000\n"), (404, "Not Found")]);
+        let client =
+            Client::new(&server.url, "abc123", stamp("get")).with_min_interval(Duration::ZERO);
+
+        assert_eq!("1000\n2000\n", client.get("/2022/day/1/input").unwrap());
+        let request = server.requests.recv().unwrap();
+        assert_eq!(
+            ("GET", "/2022/day/1/input"),
+            (request.method.as_str(), request.path.as_str())
+        );
+        assert_eq!(Some("session=abc123".to_string()), request.cookie);
+
+        let err = client.get("/2022/day/26/input").unwrap_err();
+        assert!(err.to_string().contains("not be unlocked"), "{}", err);
+    }
+
+    #[test]
+    fn requests_keep_their_distance() {
+        let server = mock::serve(vec![(200, "a"), (200, "b")]);
+        let client = Client::new(&server.url, "abc123", stamp("interval"))
+            .with_min_interval(Duration::from_millis(300));
+
+        let start = Instant::now();
+        client.get("/a").unwrap();
+        client.get("/b").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{
+    prepare, Error, Generator, Part, Prepared, Solution, Streamer, Variant, Visualizer,
+};
+
+pub fn workspace_dir() -> PathBuf {
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    return runner.parent().unwrap_or(runner).to_path_buf();
+}
+
+/// Cargo's target directory, following `CARGO_TARGET_DIR` like cargo does.
+pub fn target_dir() -> PathBuf {
+    return env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_dir().join("target"));
+}
+
+pub struct Day {
+    pub number: u8,
+    pub version: u32,
+    pub input: &'static str,
+    pub prepare: for<'a> fn(&'a str) -> Result<Prepared<'a>, Error>,
+    pub variants: fn() -> Vec<Variant>,
+    pub stream: Option<Streamer>,
+    pub generate: Option<Generator>,
+    pub visualize: Option<Visualizer>,
+}
+
+impl Day {
+    /// The day's crate, holding its `inputs/` and `answers.toml`.
+    pub fn dir(&self) -> PathBuf {
+        return workspace_dir().join(format!("day{:02}", self.number));
+    }
+
+    pub fn variants_for(&self, part: Part) -> impl Iterator<Item = Variant> {
+        return (self.variants)()
+            .into_iter()
+            .filter(move |v| v.part == part);
+    }
+}
+
+const fn day<S: Solution + 'static>() -> Day {
+    return Day {
+        number: S::DAY,
+        version: S::VERSION,
+        input: S::INPUT,
+        prepare: prepare::<S>,
+        variants: S::variants,
+        stream: S::STREAM,
+        generate: S::GENERATE,
+        visualize: S::VISUALIZE,
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day::<day01::Day01>(),
+    day::<day02::Day02>(),
+    day::<day03::Day03>(),
+    day::<day04::Day04>(),
+    day::<day05::Day05>(),
+    day::<day06::Day06>(),
+    day::<day07::Day07>(),
+    day::<day08::Day08>(),
+    day::<day09::Day09>(),
+    day::<day10::Day10>(),
+    day::<day11::Day11>(),
+    day::<day12::Day12>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    return DAYS.iter().find(|d| d.number == number);
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{cross_check, Rng};
+
+    use super::*;
+
+    #[test]
+    fn generated_inputs_give_generated_answers() {
+        for day in DAYS.iter().filter(|d| d.generate.is_some()) {
+            for seed in 0..4 {
+                let mut input = Vec::new();
+                let answers = (day.generate.unwrap())(40, &mut Rng::new(seed), &mut input).unwrap();
+                let input = String::from_utf8(input).unwrap();
+
+                for part in Part::ALL {
+                    let answer = cross_check(&(day.variants)(), part, &input)
+                        .unwrap_or_else(|e| panic!("day {} seed {}: {}", day.number, seed, e));
+                    if let Some(expected) = answers.answer(part) {
+                        assert_eq!(expected, answer, "day {} seed {}", day.number, seed);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::Error;
+
+use crate::client::{Client, YEAR};
+
+/// A file `fetch_day` looked after, and whether it had to be downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub path: PathBuf,
+    pub downloaded: bool,
+}
+
+/// Downloads the puzzle input of `day` to `dir/inputs/default.txt` and its
+/// puzzle page to `dir/puzzle.html`, skipping files already there unless
+/// `force` is set. Part two only shows up on the page once part one is solved,
+/// which is when `force` comes in.
+pub fn fetch_day(client: &Client, dir: &Path, day: u8, force: bool) -> Result<Vec<Fetched>, Error> {
+    let targets = [
+        (
+            format!("/{}/day/{}/input", YEAR, day),
+            dir.join("inputs").join("default.txt"),
+        ),
+        (format!("/{}/day/{}", YEAR, day), dir.join("puzzle.html")),
+    ];
+
+    let mut fetched = Vec::new();
+    for (url_path, path) in targets {
+        let cached = fs::metadata(&path).is_ok_and(|m| m.len() > 0);
+        if cached && !force {
+            fetched.push(Fetched {
+                path,
+                downloaded: false,
+            });
+            continue;
+        }
+
+        let body = client.get(&url_path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, body).map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        fetched.push(Fetched {
+            path,
+            downloaded: true,
+        });
+    }
+
+    return Ok(fetched);
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, time::Duration};
+
+    use super::*;
+    use crate::client::mock;
+
+    #[test]
+    fn fetch_day_downloads_once() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let server = mock::serve(vec![
+            (200, "1000\n2000\n"),
+            (200, "<article>--- Day 1 ---</article>"),
+            (200, "<article>--- Part Two ---</article>"),
+        ]);
+        let client = Client::new(&server.url, "abc123", dir.join("last-request"))
+            .with_min_interval(Duration::ZERO);
+
+        let fetched = fetch_day(&client, &dir, 1, false).unwrap();
+        assert!(fetched.iter().all(|f| f.downloaded));
+        assert_eq!(
+            "1000\n2000\n",
+            fs::read_to_string(dir.join("inputs/default.txt")).unwrap()
+        );
+        let paths: Vec<String> = server.requests.try_iter().map(|r| r.path).collect();
+        assert_eq!(vec!["/2022/day/1/input", "/2022/day/1"], paths);
+
+        let fetched = fetch_day(&client, &dir, 1, false).unwrap();
+        assert!(fetched.iter().all(|f| !f.downloaded));
+
+        fs::remove_file(dir.join("puzzle.html")).unwrap();
+        let fetched = fetch_day(&client, &dir, 1, false).unwrap();
+        assert_eq!(
+            vec![false, true],
+            fetched.iter().map(|f| f.downloaded).collect::<Vec<_>>()
+        );
+        assert!(fs::read_to_string(dir.join("puzzle.html"))
+            .unwrap()
+            .contains("Part Two"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
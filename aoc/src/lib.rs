@@ -0,0 +1,11 @@
+pub mod alloc;
+pub mod baseline;
+pub mod cache;
+pub mod client;
+pub mod days;
+pub mod fetch;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+pub mod tui;
+pub mod watch;
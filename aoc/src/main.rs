@@ -0,0 +1,855 @@
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use aoc::{
+    alloc::{measure, Measured},
+    baseline::{self, Baseline},
+    cache::{self, Cache},
+    client::Client,
+    days::{self, workspace_dir, Day, DAYS},
+    fetch, report, scaffold,
+    submit::{self, History, Outcome},
+    tui,
+    watch::{self, Change},
+};
+use aoc_common::{cross_check, known_inputs, Answers, Error, InputSource, KnownInput, Part, Rng};
+use clap::{Parser, Subcommand, ValueEnum};
+use notify::{RecursiveMode, Watcher};
+use rayon::prelude::*;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    /// Threads to solve days on; defaults to one per core. Builds with
+    /// `count-alloc` always use one, since allocations are counted
+    /// process-wide
+    #[arg(short, long, global = true)]
+    jobs: Option<usize>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every day when no day is given
+    Run {
+        /// Day to run, e.g. `7`; runs every day when omitted
+        day: Option<u8>,
+        /// Only run the given part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Variant to run, e.g. `bitwise`, instead of the day's `Solution` impl
+        #[arg(short, long)]
+        variant: Option<String>,
+        /// Puzzle input file, or `-` for stdin; defaults to `$AOC_DAYNN_INPUT`,
+        /// then the input embedded in the day crate
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+        /// Read the input bit by bit instead of loading it all, for days that
+        /// support it; stdin can only be streamed for one part
+        #[arg(short, long, requires = "day", conflicts_with = "variant")]
+        stream: bool,
+        /// Solve everything again without reading or writing cached answers
+        #[arg(long, conflicts_with_all = ["variant", "stream"])]
+        no_cache: bool,
+        /// Solve everything again and fail if any cached answer differs
+        #[arg(long, conflicts_with_all = ["variant", "stream", "no_cache"])]
+        verify: bool,
+    },
+    /// Run every registered variant and check that they agree
+    Check {
+        /// Day to check, e.g. `6`; checks every day when omitted
+        day: Option<u8>,
+        /// Puzzle input file, or `-` for stdin; defaults to `$AOC_DAYNN_INPUT`,
+        /// then the input embedded in the day crate
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+    },
+    /// List every day and its registered variants
+    List,
+    /// Time every part and variant and report their answers
+    Report {
+        /// Day to report on, e.g. `3`; reports every day when omitted
+        day: Option<u8>,
+        #[arg(short, long, value_enum, default_value_t = Format::Markdown)]
+        format: Format,
+        /// File to write the report to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Puzzle input file, or `-` for stdin; defaults to `$AOC_DAYNN_INPUT`,
+        /// then the input embedded in the day crate
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+    },
+    /// Re-run a day and its tests whenever its input or source changes,
+    /// showing which answers changed since the last run
+    Watch {
+        /// Day to watch, e.g. `7`
+        day: u8,
+        /// Only show the given part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input file to solve and watch; defaults to `$AOC_DAYNN_INPUT`,
+        /// then the input embedded in the day crate
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Skip running the day's tests
+        #[arg(long)]
+        no_test: bool,
+    },
+    /// Write a made-up input of any size, and its answers where they are
+    /// known, for stress tests and benchmarks
+    Generate {
+        /// Day to generate input for, e.g. `1`
+        day: u8,
+        /// How many records to write: elves, directories, moves, or rows and
+        /// columns of a grid
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+        /// Seed for the random choices; the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// File to write the input to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Download a day's puzzle input and page into its crate, using the
+    /// session token from `$AOC_SESSION` or `.aoc.toml`
+    Fetch {
+        /// Day to download, e.g. `5`
+        day: u8,
+        /// Download again even if the files are already there
+        #[arg(long)]
+        force: bool,
+    },
+    /// Submit an answer to the site, refusing ones that earlier attempts,
+    /// kept in the day's `submissions.toml`, already rule out
+    Submit {
+        /// Day to submit for, e.g. `5`
+        day: u8,
+        /// Part the answer is for
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Answer to submit; defaults to what the day's solution gives
+        answer: Option<String>,
+        /// Puzzle input file to solve when no answer is given, or `-` for
+        /// stdin; defaults to `$AOC_DAYNN_INPUT`, then the input embedded in
+        /// the day crate
+        #[arg(short, long, conflicts_with = "answer")]
+        input: Option<PathBuf>,
+    },
+    /// Browse days and inputs, run them and open visualizations in a
+    /// terminal UI
+    Tui,
+    /// Create the crate for a new day from a template and register it with
+    /// the workspace and this runner
+    NewDay {
+        /// Day to create, e.g. `13`
+        day: u8,
+    },
+    /// Save or compare the results of the last `cargo bench -p aoc`
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+}
+
+/// How `run` treats answers cached by earlier runs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CacheMode {
+    Use,
+    Skip,
+    Verify,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Markdown,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum BaselineAction {
+    /// Save the latest benchmark results as `baselines/<NAME>.toml`
+    Save { name: String },
+    /// Compare the latest benchmark results with a saved baseline, failing if
+    /// any got slower than the threshold allows
+    Compare {
+        name: String,
+        /// Slowdown in percent above which a benchmark counts as regressed
+        #[arg(short, long, default_value_t = 5.0)]
+        threshold: f64,
+    },
+}
+
+fn read_input(day: &Day, input: Option<&PathBuf>) -> Result<Cow<'static, str>, Error> {
+    return InputSource::resolve(day.number, input.map(PathBuf::as_path)).read(day.input);
+}
+
+// Elapsed time, followed by allocation counts when built with `count-alloc`.
+fn cost<T>(measured: &Measured<T>) -> String {
+    return match &measured.alloc {
+        Some(alloc) => format!("{:?}, {}", measured.elapsed, alloc),
+        None => format!("{:?}", measured.elapsed),
+    };
+}
+
+// A day's output, collected so that days solved in parallel still print in
+// day order.
+type Lines = Vec<String>;
+
+fn print_lines(lines: Lines) {
+    for line in lines {
+        println!("{}", line);
+    }
+}
+
+// Handles every day on the thread pool and prints their output in day order,
+// stopping at the first day that failed.
+fn for_each_day<T: Send>(
+    f: impl Fn(&mut Lines, &Day) -> Result<T, Error> + Sync,
+) -> Result<Vec<T>, Error> {
+    let done: Vec<(Lines, Result<T, Error>)> = DAYS
+        .par_iter()
+        .map(|day| {
+            let mut out = Lines::new();
+            let result = f(&mut out, day);
+            return (out, result);
+        })
+        .collect();
+
+    let mut values = Vec::new();
+    for (out, result) in done {
+        print_lines(out);
+        values.push(result?);
+    }
+    return Ok(values);
+}
+
+fn print_answer(
+    out: &mut Lines,
+    part: Part,
+    variant: Option<&str>,
+    answer: &Measured<Result<String, Error>>,
+) {
+    let label = match variant {
+        Some(name) => format!("Part {} ({})", part, name),
+        None => format!("Part {}", part),
+    };
+
+    out.push(match &answer.value {
+        Ok(value) if value.contains('\n') => {
+            format!("  {} [{}]:\n{}", label, cost(answer), value)
+        }
+        Ok(value) => format!("  {}: {} [{}]", label, value, cost(answer)),
+        Err(e) => format!("  {}: error: {}", label, e),
+    });
+}
+
+fn print_cached(out: &mut Lines, part: Part, answer: &str) {
+    if answer.contains('\n') {
+        out.push(format!("  Part {} [cached]:\n{}", part, answer));
+    } else {
+        out.push(format!("  Part {}: {} [cached]", part, answer));
+    }
+}
+
+// What `run` found wrong while printing its answers, totalled over every day
+// so it can fail once they have all been printed.
+#[derive(Clone, Copy, Default)]
+struct Tally {
+    failed: usize,
+    stale: usize,
+}
+
+impl Tally {
+    fn add(self, other: Tally) -> Tally {
+        return Tally {
+            failed: self.failed + other.failed,
+            stale: self.stale + other.stale,
+        };
+    }
+}
+
+// Answers with the day's `Solution` impl, skipping parts whose answers are
+// cached unless asked not to.
+fn solve_day(
+    out: &mut Lines,
+    day: &Day,
+    parts: &[Part],
+    input: &str,
+    cache: &Cache,
+    mode: CacheMode,
+) -> Result<Tally, Error> {
+    let keys: Vec<cache::Key> = parts
+        .iter()
+        .map(|&part| cache::Key::new(day, part, input))
+        .collect();
+    let cached: Vec<Option<String>> = match mode {
+        CacheMode::Skip => vec![None; parts.len()],
+        _ => keys
+            .iter()
+            .map(|key| cache.get(key))
+            .collect::<Result<_, _>>()?,
+    };
+
+    if mode == CacheMode::Use && cached.iter().all(Option::is_some) {
+        out.push(format!("Day {:02} [cached]", day.number));
+        for (&part, answer) in parts.iter().zip(&cached) {
+            print_cached(out, part, answer.as_deref().unwrap_or_default());
+        }
+        return Ok(Tally::default());
+    }
+
+    let parsed = measure(|| (day.prepare)(input));
+    let prepared = match &parsed.value {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            out.push(format!("Day {:02}: parse error: {}", day.number, e));
+            return Ok(Tally {
+                failed: parts.len(),
+                stale: 0,
+            });
+        }
+    };
+
+    out.push(format!("Day {:02} [parse {}]", day.number, cost(&parsed)));
+    let mut tally = Tally::default();
+    for ((&part, key), cached) in parts.iter().zip(&keys).zip(cached) {
+        if let (CacheMode::Use, Some(answer)) = (mode, &cached) {
+            print_cached(out, part, answer);
+            continue;
+        }
+
+        let solved = measure(|| prepared(part));
+        print_answer(out, part, None, &solved);
+        let Ok(answer) = &solved.value else {
+            tally.failed += 1;
+            continue;
+        };
+        if let Some(old) = cached.filter(|old| old != answer) {
+            out.push(format!("    cached answer was {}", old));
+            tally.stale += 1;
+        }
+        if mode != CacheMode::Skip {
+            cache.put(key, answer)?;
+        }
+    }
+
+    return Ok(tally);
+}
+
+fn run_day(
+    out: &mut Lines,
+    day: &Day,
+    part: Option<Part>,
+    variant: Option<&str>,
+    input: Option<&PathBuf>,
+    mode: CacheMode,
+) -> Result<Tally, Error> {
+    let input = read_input(day, input)?;
+    let parts: Vec<Part> = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let Some(name) = variant else {
+        return solve_day(out, day, &parts, &input, &Cache::open(), mode);
+    };
+
+    out.push(format!("Day {:02}", day.number));
+    let mut tally = Tally::default();
+    for part in parts {
+        let Some(selected) = day.variants_for(part).find(|v| v.name == name) else {
+            out.push(format!("  Part {}: no variant named `{}`", part, name));
+            tally.failed += 1;
+            continue;
+        };
+
+        let solved = measure(|| (selected.solve)(&input));
+        print_answer(out, part, Some(name), &solved);
+        if solved.value.is_err() {
+            tally.failed += 1;
+        }
+    }
+
+    return Ok(tally);
+}
+
+fn stream_day(day: &Day, part: Option<Part>, input: Option<&PathBuf>) -> Result<Tally, Error> {
+    let stream = day
+        .stream
+        .ok_or(format!("day {} cannot be streamed", day.number))?;
+    let source = InputSource::resolve(day.number, input.map(PathBuf::as_path));
+    let parts: Vec<Part> = match part {
+        Some(part) => vec![part],
+        None if source == InputSource::Stdin => {
+            return Err("pick a --part to stream from stdin".into())
+        }
+        None => Part::ALL.to_vec(),
+    };
+
+    println!("Day {:02} (streaming {})", day.number, source);
+    let mut tally = Tally::default();
+    for part in parts {
+        let mut reader = source.open(day.input)?;
+        let mut out = Lines::new();
+        let solved = measure(|| stream(&mut reader, part));
+        print_answer(&mut out, part, None, &solved);
+        print_lines(out);
+        if solved.value.is_err() {
+            tally.failed += 1;
+        }
+    }
+
+    return Ok(tally);
+}
+
+// Cross-checks both parts at once, since every variant only reads the input.
+fn check_input(out: &mut Lines, day: &Day, known: Option<&KnownInput>, input: &str) -> bool {
+    let variants = (day.variants)();
+    let results: Vec<Result<String, Error>> = Part::ALL
+        .par_iter()
+        .map(|&part| {
+            return match known {
+                Some(known) => known.verify(&variants, part),
+                None => cross_check(&variants, part, input),
+            };
+        })
+        .collect();
+
+    let mut agreed = true;
+    for (part, result) in Part::ALL.into_iter().zip(results) {
+        let names: Vec<&str> = day.variants_for(part).map(|v| v.name).collect();
+        let label = format!("Part {} ({})", part, names.join(" = "));
+        let note = match known {
+            Some(known) if known.answer(part).is_none() => " (no recorded answer)",
+            _ => "",
+        };
+
+        match result {
+            Ok(answer) if answer.contains('\n') => {
+                out.push(format!("    {}{}:\n{}", label, note, answer))
+            }
+            Ok(answer) => out.push(format!("    {}: {}{}", label, answer, note)),
+            Err(e) => {
+                out.push(format!("    {}", e));
+                agreed = false;
+            }
+        }
+    }
+
+    return agreed;
+}
+
+fn check_day(out: &mut Lines, day: &Day, input: Option<&PathBuf>) -> Result<bool, Error> {
+    out.push(format!("Day {:02}", day.number));
+
+    if let Some(path) = input {
+        let source = InputSource::resolve(day.number, Some(path));
+        out.push(format!("  {}", source));
+        return Ok(check_input(out, day, None, &source.read(day.input)?));
+    }
+
+    let mut agreed = true;
+    for known in known_inputs(day.dir())? {
+        out.push(format!("  {}", known.name));
+        agreed &= check_input(out, day, Some(&known), &known.input);
+    }
+
+    return Ok(agreed);
+}
+
+fn write_report(
+    days: &[&Day],
+    format: Format,
+    output: Option<&PathBuf>,
+    input: Option<&PathBuf>,
+) -> Result<(), Error> {
+    let mut rows = Vec::new();
+    for day in days {
+        rows.extend(report::report_day(day, &read_input(day, input)?));
+    }
+
+    let report = match format {
+        Format::Markdown => report::to_markdown(&rows),
+        Format::Json => format!("{:#}\n", report::to_json(&rows)),
+    };
+    match output {
+        Some(path) => fs::write(path, report)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?,
+        None => print!("{}", report),
+    }
+
+    return Ok(());
+}
+
+fn print_change(part: Part, variant: &str, change: &Change) {
+    let show = |answer: &Result<String, String>| match answer {
+        Ok(value) if value.contains('\n') => format!("\n{}", value),
+        Ok(value) => value.clone(),
+        Err(e) => format!("error: {}", e),
+    };
+    let label = format!("Part {} ({})", part, variant);
+
+    match change {
+        Change::New(answer) => println!("  {}: {}", label, show(answer)),
+        Change::Same(answer) => println!("  {}: {} (unchanged)", label, show(answer)),
+        Change::Changed(old, new) => println!("  {}: {} -> {}", label, show(old), show(new)),
+        Change::Gone(old) => println!("  {}: gone, was {}", label, show(old)),
+    }
+}
+
+fn watch_day(
+    day: &Day,
+    part: Option<Part>,
+    input: Option<&PathBuf>,
+    tests: bool,
+) -> Result<(), Error> {
+    let parts: Vec<Part> = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    let input = match InputSource::resolve(day.number, input.map(PathBuf::as_path)) {
+        InputSource::Path(path) => Some(
+            fs::canonicalize(&path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))?,
+        ),
+        InputSource::Stdin => return Err("cannot watch stdin".into()),
+        InputSource::Embedded => None,
+    };
+
+    let (sender, events) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    for path in watch::watched_paths(day, input.as_deref()) {
+        watcher.watch(&path, RecursiveMode::Recursive)?;
+    }
+
+    let mut previous = watch::Snapshot::new();
+    loop {
+        println!("Day {:02}", day.number);
+        match watch::run_report(day, input.as_deref(), &parts) {
+            Ok(current) => {
+                for ((part, variant), change) in watch::diff(&previous, &current) {
+                    print_change(*part, variant, &change);
+                }
+                previous = current;
+            }
+            Err(e) => println!("  {}", e),
+        }
+        if tests {
+            match watch::run_tests(day)? {
+                true => println!("  tests passed"),
+                false => println!("  tests failed"),
+            }
+        }
+
+        println!("Watching for changes...");
+        let changed = watch::wait_for_change(&events, |path| {
+            return watch::is_relevant(path, day, input.as_deref());
+        })?;
+        for path in changed {
+            println!("changed: {}", path.display());
+        }
+    }
+}
+
+// Writes the input to `output` or stdout, and the answers to stderr in the
+// format of `answers.toml` so they stay out of a piped input.
+fn generate_input(
+    day: &Day,
+    size: usize,
+    seed: u64,
+    output: Option<&PathBuf>,
+) -> Result<(), Error> {
+    let generate = day
+        .generate
+        .ok_or(format!("day {} has no input generator", day.number))?;
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => {
+            Box::new(BufWriter::new(File::create(path).map_err(|e| {
+                format!("could not write {}: {}", path.display(), e)
+            })?))
+        }
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    let answers = generate(size, &mut Rng::new(seed), &mut out)?;
+    out.flush()?;
+    if answers == Answers::default() {
+        eprintln!(
+            "# no answers are known for this input without solving it, e.g. with `aoc run {} --input FILE`",
+            day.number
+        );
+        return Ok(());
+    }
+    for part in Part::ALL {
+        match answers.answer(part) {
+            Some(answer) => eprintln!("{} = {}", part.name(), answer),
+            None => eprintln!("# {} is not known", part.name()),
+        }
+    }
+
+    return Ok(());
+}
+
+fn compare_baseline(name: &str, threshold: f64) -> Result<(), Error> {
+    let saved = Baseline::load(&baseline::baseline_path(name))?;
+    let current = Baseline::from_criterion(&baseline::criterion_dir())?;
+    let changes = saved.compare(&current);
+    if changes.is_empty() {
+        return Err(format!("no benchmarks in common with baseline `{}`", name).into());
+    }
+
+    let regressions: Vec<_> = changes
+        .iter()
+        .filter(|change| change.percent() > threshold)
+        .collect();
+    if regressions.is_empty() {
+        println!(
+            "{} benchmarks within {}% of baseline `{}`",
+            changes.len(),
+            threshold,
+            name
+        );
+        return Ok(());
+    }
+
+    let width = regressions.iter().map(|c| c.id.len()).max().unwrap_or(0);
+    println!(
+        "{:width$}  {:>12}  {:>12}  {:>8}",
+        "benchmark", name, "current", "change"
+    );
+    for change in &regressions {
+        println!(
+            "{:width$}  {:>12}  {:>12}  {:>+7.1}%",
+            change.id,
+            format!("{:.2?}", change.baseline),
+            format!("{:.2?}", change.current),
+            change.percent()
+        );
+    }
+
+    return Err(format!(
+        "{} of {} benchmarks regressed by more than {}%",
+        regressions.len(),
+        changes.len(),
+        threshold
+    )
+    .into());
+}
+
+fn submit_answer(
+    number: u8,
+    part: Part,
+    answer: Option<String>,
+    input: Option<&PathBuf>,
+) -> Result<(), Error> {
+    let dir = workspace_dir().join(format!("day{:02}", number));
+    if !dir.is_dir() {
+        return Err(format!("no day{:02} crate", number).into());
+    }
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = days::find(number).ok_or(format!("day {} is not solved", number))?;
+            let input = read_input(day, input)?;
+            let solve = (day.prepare)(&input)?;
+            solve(part)?
+        }
+    };
+
+    let mut history = History::load(&History::path(&dir))?;
+    println!(
+        "Day {:02} part {}: submitting {}",
+        number,
+        part,
+        answer.trim()
+    );
+    let outcome = submit::submit(&Client::from_env()?, &mut history, number, part, &answer)?;
+    println!("{}", outcome);
+    return match outcome {
+        Outcome::Correct => Ok(()),
+        _ => Err(format!("day {} part {} was not accepted", number, part).into()),
+    };
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+    let jobs = match cli.jobs {
+        _ if cfg!(feature = "count-alloc") => 1,
+        Some(jobs) => jobs,
+        None => 0,
+    };
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build_global()?;
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            variant,
+            input,
+            stream,
+            no_cache,
+            verify,
+        } => {
+            let part = part.map(Part::try_from).transpose()?;
+            let mode = match (no_cache, verify) {
+                (true, _) => CacheMode::Skip,
+                (_, true) => CacheMode::Verify,
+                _ => CacheMode::Use,
+            };
+            let tally = match day {
+                Some(number) => {
+                    let day = days::find(number).ok_or(format!("day {} is not solved", number))?;
+                    if stream {
+                        stream_day(day, part, input.as_ref())?
+                    } else {
+                        let mut out = Lines::new();
+                        let tally = run_day(
+                            &mut out,
+                            day,
+                            part,
+                            variant.as_deref(),
+                            input.as_ref(),
+                            mode,
+                        );
+                        print_lines(out);
+                        tally?
+                    }
+                }
+                None => for_each_day(|out, day| {
+                    return run_day(out, day, part, variant.as_deref(), None, mode);
+                })?
+                .into_iter()
+                .fold(Tally::default(), Tally::add),
+            };
+
+            if tally.stale > 0 {
+                return Err(format!(
+                    "{} cached answers were wrong; bump the VERSION of the days that changed",
+                    tally.stale
+                )
+                .into());
+            }
+            if tally.failed > 0 {
+                return Err("some parts failed".into());
+            }
+        }
+        Command::Check { day, input } => {
+            let agreed = match day {
+                Some(number) => {
+                    let day = days::find(number).ok_or(format!("day {} is not solved", number))?;
+                    let mut out = Lines::new();
+                    let agreed = check_day(&mut out, day, input.as_ref());
+                    print_lines(out);
+                    agreed?
+                }
+                None => for_each_day(|out, day| check_day(out, day, None))?
+                    .into_iter()
+                    .all(|agreed| agreed),
+            };
+
+            if !agreed {
+                return Err("some checks failed".into());
+            }
+        }
+        Command::List => {
+            for day in DAYS {
+                println!("Day {:02}", day.number);
+                for part in Part::ALL {
+                    let names: Vec<&str> = day.variants_for(part).map(|v| v.name).collect();
+                    if !names.is_empty() {
+                        println!("  Part {}: {}", part, names.join(", "));
+                    }
+                }
+            }
+        }
+        Command::Report {
+            day,
+            format,
+            output,
+            input,
+        } => {
+            let days: Vec<&Day> = match day {
+                Some(number) => {
+                    vec![days::find(number).ok_or(format!("day {} is not solved", number))?]
+                }
+                None => DAYS.iter().collect(),
+            };
+            write_report(&days, format, output.as_ref(), input.as_ref())?;
+        }
+        Command::Watch {
+            day,
+            part,
+            input,
+            no_test,
+        } => {
+            let part = part.map(Part::try_from).transpose()?;
+            let day = days::find(day).ok_or(format!("day {} is not solved", day))?;
+            watch_day(day, part, input.as_ref(), !no_test)?;
+        }
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let day = days::find(day).ok_or(format!("day {} is not solved", day))?;
+            generate_input(day, size, seed, output.as_ref())?;
+        }
+        Command::Fetch { day, force } => {
+            let dir = workspace_dir().join(format!("day{:02}", day));
+            if !dir.is_dir() {
+                return Err(
+                    format!("no day{:02} crate; run `aoc new-day {}` first", day, day).into(),
+                );
+            }
+            for fetched in fetch::fetch_day(&Client::from_env()?, &dir, day, force)? {
+                let status = if fetched.downloaded {
+                    "downloaded"
+                } else {
+                    "already there"
+                };
+                println!("{}: {}", fetched.path.display(), status);
+            }
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+        } => submit_answer(day, Part::try_from(part)?, answer, input.as_ref())?,
+        Command::Tui => tui::run(DAYS)?,
+        Command::NewDay { day } => {
+            for path in scaffold::new_day(&workspace_dir(), day)? {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "paste the puzzle input into day{:02}/inputs/default.txt",
+                day
+            );
+        }
+        Command::Baseline { action } => match action {
+            BaselineAction::Save { name } => {
+                let path = baseline::baseline_path(&name);
+                let current = Baseline::from_criterion(&baseline::criterion_dir())?;
+                current.save(&path)?;
+                println!(
+                    "saved {} benchmarks to {}",
+                    current.benches.len(),
+                    path.display()
+                );
+            }
+            BaselineAction::Compare { name, threshold } => compare_baseline(&name, threshold)?,
+        },
+    }
+
+    return Ok(());
+}
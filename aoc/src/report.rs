@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use aoc_common::Part;
+use serde_json::json;
+
+use crate::{
+    alloc::{format_bytes, measure, AllocStats},
+    days::Day,
+};
+
+/// The row for a day's `Solution` impl, which is timed with parsing and
+/// solving apart. Every other row times its variant end to end.
+pub const SOLUTION: &str = "solution";
+
+/// One part of one day solved by one variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub variant: &'static str,
+    pub answer: Result<String, String>,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+    pub total: Duration,
+    /// Allocations while solving; for `solution` rows this leaves out parsing.
+    pub alloc: Option<AllocStats>,
+}
+
+/// Solves both parts of `day` with its `Solution` impl and then with every
+/// registered variant.
+pub fn report_day(day: &Day, input: &str) -> Vec<Row> {
+    let mut rows = Vec::new();
+
+    let parsed = measure(|| (day.prepare)(input));
+    for part in Part::ALL {
+        let row = match &parsed.value {
+            Ok(prepared) => {
+                let solved = measure(|| prepared(part));
+                Row {
+                    day: day.number,
+                    part,
+                    variant: SOLUTION,
+                    answer: solved.value.map_err(|e| e.to_string()),
+                    parse: Some(parsed.elapsed),
+                    solve: Some(solved.elapsed),
+                    total: parsed.elapsed + solved.elapsed,
+                    alloc: solved.alloc,
+                }
+            }
+            Err(e) => Row {
+                day: day.number,
+                part,
+                variant: SOLUTION,
+                answer: Err(format!("parse error: {}", e)),
+                parse: None,
+                solve: None,
+                total: parsed.elapsed,
+                alloc: parsed.alloc,
+            },
+        };
+        rows.push(row);
+    }
+
+    for part in Part::ALL {
+        for variant in day.variants_for(part) {
+            let solved = measure(|| (variant.solve)(input));
+            rows.push(Row {
+                day: day.number,
+                part,
+                variant: variant.name,
+                answer: solved.value.map_err(|e| e.to_string()),
+                parse: None,
+                solve: None,
+                total: solved.elapsed,
+                alloc: solved.alloc,
+            });
+        }
+    }
+
+    rows.sort_by_key(|row| (row.day, row.part));
+    return rows;
+}
+
+/// One object per row, with times in nanoseconds and `null` for anything not
+/// measured.
+pub fn to_json(rows: &[Row]) -> serde_json::Value {
+    let nanos = |time: Option<Duration>| time.map(|t| t.as_nanos() as u64);
+
+    return rows
+        .iter()
+        .map(|row| {
+            return json!({
+                "day": row.day,
+                "part": u8::from(row.part),
+                "variant": row.variant,
+                "answer": row.answer.as_ref().ok(),
+                "error": row.answer.as_ref().err(),
+                "parse_ns": nanos(row.parse),
+                "solve_ns": nanos(row.solve),
+                "total_ns": row.total.as_nanos() as u64,
+                "allocations": row.alloc.map(|a| a.allocations),
+                "allocated_bytes": row.alloc.map(|a| a.bytes),
+                "peak_bytes": row.alloc.map(|a| a.peak),
+            });
+        })
+        .collect();
+}
+
+pub fn to_markdown(rows: &[Row]) -> String {
+    let time = |time: Option<Duration>| time.map_or("-".to_string(), |t| format!("{:.2?}", t));
+
+    let mut table = String::from(
+        "| Day | Part | Variant | Answer | Parse | Solve | Total | Allocs | Allocated | Peak |\n\
+         |----:|-----:|---------|--------|------:|------:|------:|-------:|----------:|-----:|\n",
+    );
+    for row in rows {
+        let alloc =
+            |field: fn(&AllocStats) -> String| row.alloc.as_ref().map_or("-".to_string(), field);
+        let answer = match &row.answer {
+            Ok(answer) => format!("<code>{}</code>", escape(answer)),
+            Err(e) => format!("error: {}", escape(e)),
+        };
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            row.day,
+            row.part,
+            row.variant,
+            answer,
+            time(row.parse),
+            time(row.solve),
+            time(Some(row.total)),
+            alloc(|a| a.allocations.to_string()),
+            alloc(|a| format_bytes(a.bytes)),
+            alloc(|a| format_bytes(a.peak)),
+        ));
+    }
+
+    return table;
+}
+
+// Keeps multi-line answers, like day 10's CRT, inside their table cell.
+fn escape(text: &str) -> String {
+    return text
+        .trim_end()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('|', "\\|")
+        .replace('\n', "<br>");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        return vec![
+            Row {
+                day: 10,
+                part: Part::Two,
+                variant: SOLUTION,
+                answer: Ok("#..#\n.##.\n".to_string()),
+                parse: Some(Duration::from_micros(3)),
+                solve: Some(Duration::from_nanos(1500)),
+                total: Duration::from_nanos(4500),
+                alloc: None,
+            },
+            Row {
+                day: 12,
+                part: Part::One,
+                variant: "iterator",
+                answer: Err("no path from start to end".to_string()),
+                parse: None,
+                solve: None,
+                total: Duration::from_micros(20),
+                alloc: Some(AllocStats {
+                    allocations: 3,
+                    bytes: 4096,
+                    peak: 2048,
+                }),
+            },
+        ];
+    }
+
+    #[test]
+    fn to_markdown_keeps_one_row_per_line() {
+        let table = to_markdown(&rows());
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(4, lines.len());
+        assert_eq!(
+            "| 10 | 2 | solution | <code>#..#<br>.##.</code> | 3.00µs | 1.50µs | 4.50µs | - | - | - |",
+            lines[2]
+        );
+        assert_eq!(
+            "| 12 | 1 | iterator | error: no path from start to end | - | - | 20.00µs | 3 | 4.0 KiB | 2.0 KiB |",
+            lines[3]
+        );
+    }
+
+    #[test]
+    fn to_json_uses_null_for_unmeasured() {
+        let json = to_json(&rows());
+        assert_eq!(
+            json!({
+                "day": 12,
+                "part": 1,
+                "variant": "iterator",
+                "answer": null,
+                "error": "no path from start to end",
+                "parse_ns": null,
+                "solve_ns": null,
+                "total_ns": 20000,
+                "allocations": 3,
+                "allocated_bytes": 4096,
+                "peak_bytes": 2048,
+            }),
+            json[1]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::Error;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.in");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.in");
+const ANSWERS_TEMPLATE: &str = include_str!("../templates/answers.toml.in");
+
+fn fill(template: &str, number: u8) -> String {
+    return template
+        .replace("__NN__", &format!("{:02}", number))
+        .replace("__DAY__", &number.to_string());
+}
+
+/// Inserts `line` among the lines of `text` that start with `prefix` once
+/// trimmed, keeping those lines sorted.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String, Error> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let matching: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].trim_start().starts_with(prefix))
+        .collect();
+    let last = *matching
+        .last()
+        .ok_or(format!("no lines starting with `{}`", prefix))?;
+
+    if let Some(&i) = matching.iter().find(|&&i| lines[i].trim() == line.trim()) {
+        return Err(format!("line {} already lists `{}`", i + 1, line.trim()).into());
+    }
+    let at = matching
+        .iter()
+        .copied()
+        .find(|&i| lines[i].trim() > line.trim())
+        .unwrap_or(last + 1);
+    lines.insert(at, line);
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    return Ok(text);
+}
+
+fn edit(path: &Path, prefix: &str, line: &str) -> Result<(PathBuf, String), Error> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    let edited =
+        insert_sorted(&text, prefix, line).map_err(|e| format!("{}: {}", path.display(), e))?;
+    return Ok((path.to_path_buf(), edited));
+}
+
+/// Creates the `dayNN` crate for `number` under `workspace` and registers it
+/// with the workspace and the runner, returning every file written. Nothing is
+/// written unless all of the edits apply.
+pub fn new_day(workspace: &Path, number: u8) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&number) {
+        return Err(format!("day must be from 1 to 25, got {}", number).into());
+    }
+    let name = format!("day{:02}", number);
+    let dir = workspace.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    let edits = [
+        edit(
+            &workspace.join("Cargo.toml"),
+            "\"day",
+            &format!("    \"{}\",", name),
+        )?,
+        edit(
+            &workspace.join("aoc").join("Cargo.toml"),
+            "day",
+            &format!("{} = {{ path = \"../{}\" }}", name, name),
+        )?,
+        edit(
+            &workspace.join("aoc").join("src").join("days.rs"),
+            "day::<",
+            &format!("    day::<{}::Day{:02}>(),", name, number),
+        )?,
+    ];
+    let files = [
+        (dir.join("Cargo.toml"), fill(CARGO_TEMPLATE, number)),
+        (dir.join("answers.toml"), fill(ANSWERS_TEMPLATE, number)),
+        (dir.join("src").join("lib.rs"), fill(LIB_TEMPLATE, number)),
+        (dir.join("inputs").join("default.txt"), String::new()),
+        (dir.join("inputs").join("example.txt"), String::new()),
+    ];
+
+    let mut written = Vec::new();
+    for (path, contents) in files.into_iter().chain(edits) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+
+    return Ok(written);
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn insert_sorted_keeps_order() {
+        let members = "members = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n]\n";
+        assert_eq!(
+            "members = [\n    \"aoc\",\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n",
+            insert_sorted(members, "\"day", "    \"day02\",").unwrap()
+        );
+        assert_eq!(
+            "members = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n    \"day04\",\n]\n",
+            insert_sorted(members, "\"day", "    \"day04\",").unwrap()
+        );
+        assert!(insert_sorted(members, "\"day", "    \"day03\",").is_err());
+        assert!(insert_sorted(members, "day::<", "    day::<day02::Day02>(),").is_err());
+    }
+
+    #[test]
+    fn new_day_writes_crate_and_registers_it() {
+        let workspace = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let runner = workspace.join("aoc");
+        fs::create_dir_all(runner.join("src")).unwrap();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "members = [\n    \"aoc\",\n    \"day01\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            runner.join("Cargo.toml"),
+            "[dependencies]\nday01 = { path = \"../day01\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            runner.join("src").join("days.rs"),
+            "pub const DAYS: &[Day] = &[\n    day::<day01::Day01>(),\n];\n",
+        )
+        .unwrap();
+
+        new_day(&workspace, 13).unwrap();
+
+        let days = fs::read_to_string(runner.join("src").join("days.rs")).unwrap();
+        assert!(days.contains("    day::<day01::Day01>(),\n    day::<day13::Day13>(),\n"));
+        let lib = fs::read_to_string(workspace.join("day13/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day13;") && lib.contains("const DAY: u8 = 13;"));
+        assert!(workspace.join("day13/inputs/default.txt").exists());
+        assert!(new_day(&workspace, 13).is_err());
+        assert!(new_day(&workspace, 26).is_err());
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+}
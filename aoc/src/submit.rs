@@ -0,0 +1,379 @@
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::{Error, Part};
+
+use crate::client::{Client, YEAR};
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answered too soon after a wrong answer; holds the seconds left.
+    Wait(u64),
+    /// The part was already solved, or part one is not yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::Wait(_) => "wait",
+            Outcome::WrongLevel => "wrong level",
+        };
+    }
+
+    fn from_name(name: &str, wait: u64) -> Option<Self> {
+        return match name {
+            "correct" => Some(Outcome::Correct),
+            "too high" => Some(Outcome::TooHigh),
+            "too low" => Some(Outcome::TooLow),
+            "wrong" => Some(Outcome::Wrong),
+            "wait" => Some(Outcome::Wait(wait)),
+            "wrong level" => Some(Outcome::WrongLevel),
+            _ => None,
+        };
+    }
+
+    fn is_wrong(&self) -> bool {
+        return matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong);
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Outcome::Correct => write!(f, "that's the right answer"),
+            Outcome::TooHigh => write!(f, "wrong, the answer is too high"),
+            Outcome::TooLow => write!(f, "wrong, the answer is too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(seconds) => write!(f, "answered too recently, {}s left to wait", seconds),
+            Outcome::WrongLevel => write!(f, "this part is already solved or not unlocked"),
+        };
+    }
+}
+
+// Reads `1m 32s` or `32s`, as in "You have 1m 32s left to wait".
+fn parse_wait(html: &str) -> Option<u64> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in html[start..end].split_whitespace() {
+        let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    return Some(seconds);
+}
+
+/// Reads the outcome out of the page the site answers a submission with.
+pub fn parse_response(html: &str) -> Result<Outcome, Error> {
+    if html.contains("That's the right answer") {
+        return Ok(Outcome::Correct);
+    }
+    if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            return Ok(Outcome::TooHigh);
+        }
+        if html.contains("your answer is too low") {
+            return Ok(Outcome::TooLow);
+        }
+        return Ok(Outcome::Wrong);
+    }
+    if html.contains("You gave an answer too recently") {
+        return Ok(Outcome::Wait(parse_wait(html).unwrap_or(60)));
+    }
+    if html.contains("You don't seem to be solving the right level") {
+        return Ok(Outcome::WrongLevel);
+    }
+
+    return Err("could not make sense of the site's response".into());
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub at: u64,
+}
+
+pub fn now() -> u64 {
+    return SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+}
+
+/// Every answer submitted for one day, kept in its `submissions.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn path(day_dir: &Path) -> PathBuf {
+        return day_dir.join("submissions.toml");
+    }
+
+    /// Starts an empty history if `path` does not exist yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let mut history = History {
+            path: path.to_path_buf(),
+            attempts: Vec::new(),
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(history),
+            Err(e) => return Err(format!("could not read {}: {}", path.display(), e).into()),
+        };
+
+        let invalid = |message: &str| format!("invalid {}: {}", path.display(), message);
+        let table: toml::Table = text.parse().map_err(|e| invalid(&format!("{}", e)))?;
+        let attempts = match table.get("attempt") {
+            Some(attempts) => attempts
+                .as_array()
+                .ok_or_else(|| invalid("`attempt` must be an array of tables"))?
+                .as_slice(),
+            None => &[],
+        };
+
+        for attempt in attempts {
+            let field = |name: &str| attempt.get(name).ok_or_else(|| invalid(name));
+            let part = field("part")?.as_integer().ok_or_else(|| invalid("part"))?;
+            let part = u8::try_from(part).map_err(|_| invalid("part"))?;
+            let wait = attempt
+                .get("wait")
+                .and_then(|w| w.as_integer())
+                .unwrap_or(0);
+            let outcome = field("outcome")?
+                .as_str()
+                .and_then(|o| Outcome::from_name(o, wait as u64))
+                .ok_or_else(|| invalid("outcome"))?;
+
+            history.attempts.push(Attempt {
+                part: Part::try_from(part)?,
+                answer: field("answer")?
+                    .as_str()
+                    .ok_or_else(|| invalid("answer"))?
+                    .to_string(),
+                outcome,
+                at: field("at")?.as_integer().ok_or_else(|| invalid("at"))? as u64,
+            });
+        }
+
+        return Ok(history);
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let attempts: Vec<toml::Value> = self
+            .attempts
+            .iter()
+            .map(|attempt| {
+                let mut table = toml::Table::new();
+                table.insert("part".into(), i64::from(u8::from(attempt.part)).into());
+                table.insert("answer".into(), attempt.answer.clone().into());
+                table.insert("outcome".into(), attempt.outcome.name().into());
+                if let Outcome::Wait(seconds) = attempt.outcome {
+                    table.insert("wait".into(), (seconds as i64).into());
+                }
+                table.insert("at".into(), (attempt.at as i64).into());
+                return toml::Value::Table(table);
+            })
+            .collect();
+
+        let mut document = toml::Table::new();
+        document.insert("attempt".into(), attempts.into());
+        return fs::write(&self.path, toml::to_string(&document)?)
+            .map_err(|e| format!("could not write {}: {}", self.path.display(), e).into());
+    }
+
+    /// Fails if submitting `answer` for `part` at `now` cannot be right or
+    /// would only earn a timeout: the part is solved, the answer was already
+    /// wrong or lies outside the bounds earlier answers set, or the site
+    /// asked to wait.
+    pub fn check(&self, part: Part, answer: &str, now: u64) -> Result<(), Error> {
+        let attempts: Vec<&Attempt> = self.attempts.iter().filter(|a| a.part == part).collect();
+
+        if let Some(correct) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+            return Err(format!("part {} is already solved: {}", part, correct.answer).into());
+        }
+        if let Some(wrong) = attempts
+            .iter()
+            .find(|a| a.answer == answer && a.outcome.is_wrong())
+        {
+            return Err(format!("{} was already {}", answer, wrong.outcome.name()).into());
+        }
+        for attempt in &attempts {
+            if let Outcome::Wait(seconds) = attempt.outcome {
+                let until = attempt.at + seconds;
+                if until > now {
+                    return Err(format!("the site asked to wait {}s more", until - now).into());
+                }
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |outcome: Outcome| {
+                return attempts
+                    .iter()
+                    .filter(move |a| a.outcome == outcome)
+                    .filter_map(|a| a.answer.parse::<i128>().ok());
+            };
+            if let Some(high) = bound(Outcome::TooHigh).min().filter(|&high| value >= high) {
+                return Err(format!("{} is not below {}, which was too high", value, high).into());
+            }
+            if let Some(low) = bound(Outcome::TooLow).max().filter(|&low| value <= low) {
+                return Err(format!("{} is not above {}, which was too low", value, low).into());
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/// Posts `answer` for `part` of `day` unless `history` rules it out, and
+/// records what came of it.
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Outcome, Error> {
+    let answer = answer.trim();
+    history.check(part, answer, now())?;
+
+    let level = u8::from(part).to_string();
+    let html = client.post_form(
+        &format!("/{}/day/{}/answer", YEAR, day),
+        &[("level", &level), ("answer", answer)],
+    )?;
+    let outcome = parse_response(&html)?;
+
+    history.attempts.push(Attempt {
+        part,
+        answer: answer.to_string(),
+        outcome,
+        at: now(),
+    });
+    history.save()?;
+    return Ok(outcome);
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, time::Duration};
+
+    use super::*;
+    use crate::client::mock;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.</p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer! You are one gold star closer \
+        to collecting enough star fruit.</p></article>";
+    const WAIT: &str = "<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 32s left to wait.</p></article>";
+
+    fn attempt(part: Part, answer: &str, outcome: Outcome, at: u64) -> Attempt {
+        return Attempt {
+            part,
+            answer: answer.to_string(),
+            outcome,
+            at,
+        };
+    }
+
+    #[test]
+    fn parse_response_reads_outcome() {
+        assert_eq!(Outcome::TooHigh, parse_response(TOO_HIGH).unwrap());
+        assert_eq!(Outcome::Correct, parse_response(CORRECT).unwrap());
+        assert_eq!(Outcome::Wait(92), parse_response(WAIT).unwrap());
+        assert!(parse_response("<html>Log in</html>").is_err());
+    }
+
+    #[test]
+    fn check_refuses_hopeless_answers() {
+        let history = History {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt(Part::One, "500", Outcome::TooHigh, 0),
+                attempt(Part::One, "100", Outcome::TooLow, 60),
+                attempt(Part::One, "abc", Outcome::Wrong, 120),
+                attempt(Part::One, "300", Outcome::Wait(60), 180),
+                attempt(Part::Two, "42", Outcome::Correct, 300),
+            ],
+        };
+
+        assert!(history.check(Part::One, "300", 1000).is_ok());
+        assert!(history.check(Part::One, "600", 1000).is_err());
+        assert!(history.check(Part::One, "100", 1000).is_err());
+        assert!(history.check(Part::One, "abc", 1000).is_err());
+        assert!(history.check(Part::One, "xyz", 1000).is_ok());
+        let err = history.check(Part::One, "300", 200).unwrap_err();
+        assert_eq!("the site asked to wait 40s more", err.to_string());
+        assert!(history.check(Part::Two, "43", 1000).is_err());
+    }
+
+    #[test]
+    fn load_only_starts_empty_without_a_file() {
+        let missing = env::temp_dir().join("aoc-no-such-submissions.toml");
+        assert!(History::load(&missing).unwrap().attempts.is_empty());
+
+        let err = History::load(&env::temp_dir()).unwrap_err();
+        assert!(err.to_string().starts_with("could not read"), "{}", err);
+    }
+
+    #[test]
+    fn submit_posts_and_records_attempts() {
+        let path = env::temp_dir().join(format!("aoc-submissions-{}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let server = mock::serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let client = Client::new(&server.url, "abc123", path.with_extension("stamp"))
+            .with_min_interval(Duration::ZERO);
+        let mut history = History::load(&path).unwrap();
+
+        assert_eq!(
+            Outcome::TooHigh,
+            submit(&client, &mut history, 1, Part::One, "75000\n").unwrap()
+        );
+        let request = server.requests.recv().unwrap();
+        assert_eq!("/2022/day/1/answer", request.path);
+        assert_eq!("level=1&answer=75000", request.body);
+
+        assert!(submit(&client, &mut history, 1, Part::One, "80000").is_err());
+        assert_eq!(
+            Outcome::Correct,
+            submit(&client, &mut history, 1, Part::One, "71924").unwrap()
+        );
+
+        let saved = History::load(&path).unwrap();
+        assert_eq!(history, saved);
+        assert_eq!(
+            vec![Outcome::TooHigh, Outcome::Correct],
+            saved.attempts.iter().map(|a| a.outcome).collect::<Vec<_>>()
+        );
+
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(path.with_extension("stamp"));
+    }
+}
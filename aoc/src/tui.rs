@@ -0,0 +1,545 @@
+use aoc_common::{known_inputs, Error, KnownInput, Part};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Cell, Clear, List, ListState, Paragraph, Row as TableRow, Table, TableState},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    days::Day,
+    report::{report_day, Row},
+};
+
+const HELP: &str = "↑↓ select  tab switch  enter run  1/2 run one part  v visualize  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Days,
+    Inputs,
+    Results,
+}
+
+/// How an answer compares with the one recorded for its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    Unknown,
+    Failed,
+}
+
+impl Verdict {
+    fn symbol(self) -> Span<'static> {
+        return match self {
+            Verdict::Right => Span::styled("✓", Style::new().fg(Color::Green)),
+            Verdict::Wrong => Span::styled("✗", Style::new().fg(Color::Red)),
+            Verdict::Unknown => Span::styled("?", Style::new().fg(Color::DarkGray)),
+            Verdict::Failed => Span::styled("error", Style::new().fg(Color::Red)),
+        };
+    }
+}
+
+/// Compares answers the way `answers.toml` does, ignoring trailing
+/// whitespace.
+pub fn verdict(answer: &Result<String, String>, expected: Option<&str>) -> Verdict {
+    return match (answer, expected) {
+        (Err(_), _) => Verdict::Failed,
+        (Ok(_), None) => Verdict::Unknown,
+        (Ok(answer), Some(expected)) if answer.trim_end() == expected.trim_end() => Verdict::Right,
+        (Ok(_), Some(_)) => Verdict::Wrong,
+    };
+}
+
+/// Lines of `expected` and `actual` side by side: `' '` where they agree,
+/// otherwise `'-'` for the expected line followed by `'+'` for the actual one.
+pub fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<(char, &'a str)> {
+    let expected: Vec<&str> = expected.trim_end().lines().collect();
+    let actual: Vec<&str> = actual.trim_end().lines().collect();
+    let mut lines = Vec::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => lines.push((' ', *e)),
+            (e, a) => {
+                lines.extend(e.map(|e| ('-', *e)));
+                lines.extend(a.map(|a| ('+', *a)));
+            }
+        }
+    }
+    return lines;
+}
+
+/// A day's visualization, drawn over everything else until closed.
+struct Overlay {
+    title: String,
+    text: String,
+    scroll: u16,
+}
+
+pub struct App {
+    days: &'static [Day],
+    day: usize,
+    inputs: Vec<KnownInput>,
+    input: usize,
+    focus: Focus,
+    rows: Vec<Row>,
+    result: usize,
+    overlay: Option<Overlay>,
+    /// Parts to solve on the next pass of the event loop, once the screen
+    /// says they are being solved.
+    pending: Option<Vec<Part>>,
+    status: String,
+    quit: bool,
+}
+
+impl App {
+    pub fn new(days: &'static [Day]) -> Self {
+        let mut app = App {
+            days,
+            day: 0,
+            inputs: Vec::new(),
+            input: 0,
+            focus: Focus::Days,
+            rows: Vec::new(),
+            result: 0,
+            overlay: None,
+            pending: None,
+            status: String::new(),
+            quit: false,
+        };
+        app.select_day(0);
+        return app;
+    }
+
+    fn day(&self) -> &'static Day {
+        return &self.days[self.day];
+    }
+
+    fn known(&self) -> Option<&KnownInput> {
+        return self.inputs.get(self.input);
+    }
+
+    fn select_day(&mut self, index: usize) {
+        self.day = index;
+        self.input = 0;
+        self.rows.clear();
+        self.result = 0;
+        self.status.clear();
+        self.inputs = match known_inputs(self.day().dir()) {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                self.status = format!("could not read inputs: {}", e);
+                Vec::new()
+            }
+        };
+    }
+
+    fn select_input(&mut self, index: usize) {
+        self.input = index;
+        self.rows.clear();
+        self.result = 0;
+        self.status.clear();
+    }
+
+    fn move_selection(&mut self, up: bool) {
+        let step = |current: usize, len: usize| match up {
+            true => current.saturating_sub(1),
+            false => (current + 1).min(len.saturating_sub(1)),
+        };
+        match self.focus {
+            Focus::Days => self.select_day(step(self.day, self.days.len())),
+            Focus::Inputs => self.select_input(step(self.input, self.inputs.len())),
+            Focus::Results => self.result = step(self.result, self.rows.len()),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        if let Some(overlay) = &mut self.overlay {
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => self.overlay = None,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    overlay.scroll = overlay.scroll.saturating_sub(1)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    overlay.scroll = overlay.scroll.saturating_add(1)
+                }
+                KeyCode::PageUp => overlay.scroll = overlay.scroll.saturating_sub(20),
+                KeyCode::PageDown => overlay.scroll = overlay.scroll.saturating_add(20),
+                KeyCode::Home => overlay.scroll = 0,
+                _ => {}
+            }
+            return;
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.quit = true,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                self.focus = match self.focus {
+                    Focus::Days => Focus::Inputs,
+                    Focus::Inputs => Focus::Results,
+                    Focus::Results => Focus::Days,
+                };
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                self.focus = match self.focus {
+                    Focus::Days => Focus::Results,
+                    Focus::Inputs => Focus::Days,
+                    Focus::Results => Focus::Inputs,
+                };
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(true),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(false),
+            KeyCode::Enter | KeyCode::Char('r') => self.request(Part::ALL.to_vec()),
+            KeyCode::Char('1') => self.request(vec![Part::One]),
+            KeyCode::Char('2') => self.request(vec![Part::Two]),
+            KeyCode::Char('v') => self.visualize(),
+            _ => {}
+        }
+    }
+
+    fn request(&mut self, parts: Vec<Part>) {
+        if self.known().is_none() {
+            self.status = format!("day {:02} has no inputs", self.day().number);
+            return;
+        }
+        self.status = "solving...".to_string();
+        self.pending = Some(parts);
+    }
+
+    /// Solves whatever the last key asked for, returning whether there was
+    /// anything to do.
+    pub fn run_pending(&mut self) -> bool {
+        let Some(parts) = self.pending.take() else {
+            return false;
+        };
+        let Some(known) = self.known() else {
+            return true;
+        };
+
+        let rows: Vec<Row> = report_day(self.day(), &known.input)
+            .into_iter()
+            .filter(|row| parts.contains(&row.part))
+            .collect();
+        let verdicts: Vec<Verdict> = rows
+            .iter()
+            .map(|row| verdict(&row.answer, known.answer(row.part)))
+            .collect();
+        let count = |v: Verdict| verdicts.iter().filter(|&&x| x == v).count();
+        self.status = format!(
+            "{} answers on {}: {} right, {} wrong, {} failed, {} unknown",
+            rows.len(),
+            known.name,
+            count(Verdict::Right),
+            count(Verdict::Wrong),
+            count(Verdict::Failed),
+            count(Verdict::Unknown),
+        );
+        self.rows = rows;
+        self.result = 0;
+        self.focus = Focus::Results;
+        return true;
+    }
+
+    fn visualize(&mut self) {
+        let day = self.day();
+        let Some(visualize) = day.visualize else {
+            self.status = format!("day {:02} has no visualization", day.number);
+            return;
+        };
+        let Some(known) = self.known() else {
+            self.status = format!("day {:02} has no inputs", day.number);
+            return;
+        };
+        match visualize(&known.input) {
+            Ok(text) => {
+                self.overlay = Some(Overlay {
+                    title: format!(
+                        " Day {:02} on {}: ↑↓ scroll, esc close ",
+                        day.number, known.name
+                    ),
+                    text,
+                    scroll: 0,
+                });
+            }
+            Err(e) => self.status = format!("could not visualize: {}", e),
+        }
+    }
+
+    fn block(&self, title: &str, focus: Focus) -> Block<'static> {
+        let block = Block::bordered().title(format!(" {} ", title));
+        if self.focus == focus {
+            return block.border_style(Style::new().fg(Color::Yellow));
+        }
+        return block;
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [days, right] =
+            Layout::horizontal([Constraint::Length(12), Constraint::Min(0)]).areas(main);
+        let [top, results, detail] = Layout::vertical([
+            Constraint::Length(7),
+            Constraint::Min(5),
+            Constraint::Length(10),
+        ])
+        .areas(right);
+        let [inputs, variants] =
+            Layout::horizontal([Constraint::Length(24), Constraint::Min(0)]).areas(top);
+
+        self.draw_days(frame, days);
+        self.draw_inputs(frame, inputs);
+        self.draw_variants(frame, variants);
+        self.draw_results(frame, results);
+        self.draw_detail(frame, detail);
+
+        let status_line = match self.status.is_empty() {
+            true => HELP,
+            false => &self.status,
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+
+        if let Some(overlay) = &self.overlay {
+            let area = frame.area();
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(overlay.text.as_str())
+                    .block(Block::bordered().title(overlay.title.as_str()))
+                    .scroll((overlay.scroll, 0)),
+                area,
+            );
+        }
+    }
+
+    fn draw_days(&self, frame: &mut Frame, area: Rect) {
+        let items = self.days.iter().map(|day| match day.visualize {
+            Some(_) => format!("Day {:02} *", day.number),
+            None => format!("Day {:02}", day.number),
+        });
+        let list = List::new(items)
+            .block(self.block("Days", Focus::Days))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.day));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_inputs(&self, frame: &mut Frame, area: Rect) {
+        let items = self.inputs.iter().map(|known| known.name.as_str());
+        let list = List::new(items)
+            .block(self.block("Inputs", Focus::Inputs))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.input));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_variants(&self, frame: &mut Frame, area: Rect) {
+        let day = self.day();
+        let mut lines = Vec::new();
+        for part in Part::ALL {
+            let names: Vec<&str> = day.variants_for(part).map(|v| v.name).collect();
+            lines.push(Line::from(format!("Part {}: {}", part, names.join(", "))));
+        }
+        if let Some(known) = self.known() {
+            let expected = |part| match known.answer(part) {
+                Some(answer) if answer.contains('\n') => "(several lines)".to_string(),
+                Some(answer) => answer.to_string(),
+                None => "?".to_string(),
+            };
+            lines.push(Line::from(format!(
+                "Expected: {} / {}",
+                expected(Part::One),
+                expected(Part::Two)
+            )));
+        }
+        if day.visualize.is_some() {
+            lines.push(Line::from("* press v for a visualization"));
+        }
+        let title = format!("Day {:02}", day.number);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(format!(" {} ", title))),
+            area,
+        );
+    }
+
+    fn draw_results(&self, frame: &mut Frame, area: Rect) {
+        let expected = |row: &Row| self.known().and_then(|known| known.answer(row.part));
+        let rows = self.rows.iter().map(|row| {
+            let answer = match &row.answer {
+                Ok(answer) if answer.contains('\n') => {
+                    format!("{} ...", answer.lines().next().unwrap_or_default())
+                }
+                Ok(answer) => answer.clone(),
+                Err(e) => e.clone(),
+            };
+            return TableRow::new([
+                Cell::from(row.part.to_string()),
+                Cell::from(row.variant),
+                Cell::from(answer),
+                Cell::from(format!("{:.2?}", row.total)),
+                Cell::from(verdict(&row.answer, expected(row)).symbol()),
+            ]);
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(14),
+            Constraint::Min(10),
+            Constraint::Length(12),
+            Constraint::Length(5),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                TableRow::new(["Part", "Variant", "Answer", "Time", ""])
+                    .style(Style::new().add_modifier(Modifier::BOLD)),
+            )
+            .block(self.block("Results", Focus::Results))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let selected = (!self.rows.is_empty()).then_some(self.result);
+        let mut state = TableState::default().with_selected(selected);
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    // The whole answer of the selected result, as a diff when it is wrong.
+    fn draw_detail(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" Answer ");
+        let Some(row) = self.rows.get(self.result) else {
+            frame.render_widget(block, area);
+            return;
+        };
+        let expected = self.known().and_then(|known| known.answer(row.part));
+        let text = match (&row.answer, expected) {
+            (Err(e), _) => Text::styled(e.as_str(), Style::new().fg(Color::Red)),
+            (Ok(answer), Some(expected))
+                if verdict(&row.answer, Some(expected)) == Verdict::Wrong =>
+            {
+                let lines = diff_lines(expected, answer)
+                    .into_iter()
+                    .map(|(sign, line)| {
+                        let style = match sign {
+                            '-' => Style::new().fg(Color::Red),
+                            '+' => Style::new().fg(Color::Green),
+                            _ => Style::new(),
+                        };
+                        return Line::styled(format!("{} {}", sign, line), style);
+                    });
+                Text::from_iter(lines)
+            }
+            (Ok(answer), _) => Text::from(answer.as_str()),
+        };
+        frame.render_widget(Paragraph::new(text).block(block), area);
+    }
+}
+
+/// Takes over the terminal until `q` is pressed.
+pub fn run(days: &'static [Day]) -> Result<(), Error> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, App::new(days));
+    ratatui::restore();
+    return result;
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> Result<(), Error> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        if app.run_pending() {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key.code);
+            }
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::days::DAYS;
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        return buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>() + "\n")
+            .collect();
+    }
+
+    fn select_day(app: &mut App, number: u8) {
+        while app.day().number != number {
+            app.handle_key(KeyCode::Down);
+        }
+    }
+
+    #[test]
+    fn verdict_ignores_trailing_whitespace() {
+        assert_eq!(Verdict::Right, verdict(&Ok("ab\n".into()), Some("ab")));
+        assert_eq!(Verdict::Wrong, verdict(&Ok("ac".into()), Some("ab")));
+        assert_eq!(Verdict::Unknown, verdict(&Ok("ab".into()), None));
+        assert_eq!(Verdict::Failed, verdict(&Err("boom".into()), Some("ab")));
+    }
+
+    #[test]
+    fn diff_lines_marks_changed_lines() {
+        assert_eq!(
+            vec![(' ', "#.#"), ('-', "..."), ('+', ".#."), ('+', "##")],
+            diff_lines("#.#\n...\n", "#.#\n.#.\n##")
+        );
+    }
+
+    #[test]
+    fn keys_move_between_days_and_run_them() {
+        let mut app = App::new(DAYS);
+        select_day(&mut app, 5);
+        app.handle_key(KeyCode::Tab);
+        assert_eq!(Focus::Inputs, app.focus);
+        assert_eq!("default", app.known().unwrap().name);
+
+        app.handle_key(KeyCode::Char('1'));
+        assert!(app.run_pending());
+        assert!(!app.run_pending());
+        assert_eq!(Focus::Results, app.focus);
+        assert!(app.rows.iter().all(|row| row.part == Part::One));
+        assert!(app.rows.iter().all(|row| verdict(
+            &row.answer,
+            app.known().unwrap().answer(Part::One)
+        ) == Verdict::Right));
+
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn draws_results_and_visualization() {
+        let mut app = App::new(DAYS);
+        select_day(&mut app, 10);
+        app.handle_key(KeyCode::Enter);
+        app.run_pending();
+        let drawn = screen(&app);
+        assert!(drawn.contains("Day 10 *"));
+        assert!(drawn.contains("iterator"));
+        assert!(drawn.contains("✓"));
+        assert!(drawn.contains("4 answers on default: 4 right"));
+
+        app.handle_key(KeyCode::Char('v'));
+        assert!(screen(&app).contains("████ ████ █  █  ██  █    ███   ██  ███"));
+        app.handle_key(KeyCode::Esc);
+        assert!(app.overlay.is_none());
+        assert!(!app.quit);
+    }
+
+    #[test]
+    fn visualize_without_one_says_so() {
+        let mut app = App::new(DAYS);
+        app.handle_key(KeyCode::Char('v'));
+        assert!(app.overlay.is_none());
+        assert_eq!("day 01 has no visualization", app.status);
+    }
+}
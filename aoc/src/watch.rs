@@ -0,0 +1,279 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    time::Duration,
+};
+
+use aoc_common::{Error, Part};
+use notify::{Event, EventKind};
+
+use crate::days::{workspace_dir, Day};
+
+/// Editors tend to save in several steps, so changes this close together are
+/// handled as one.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// Every answer from one run, keyed by part and variant, with errors as text.
+pub type Snapshot = BTreeMap<(Part, String), Result<String, String>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<'a> {
+    New(&'a Result<String, String>),
+    Same(&'a Result<String, String>),
+    Changed(&'a Result<String, String>, &'a Result<String, String>),
+    Gone(&'a Result<String, String>),
+}
+
+/// Pairs every answer in `previous` or `current` with how it changed.
+pub fn diff<'a>(
+    previous: &'a Snapshot,
+    current: &'a Snapshot,
+) -> Vec<(&'a (Part, String), Change<'a>)> {
+    let mut changes: Vec<_> = current
+        .iter()
+        .map(|(key, answer)| {
+            let change = match previous.get(key) {
+                None => Change::New(answer),
+                Some(old) if old == answer => Change::Same(answer),
+                Some(old) => Change::Changed(old, answer),
+            };
+            return (key, change);
+        })
+        .collect();
+    changes.extend(
+        previous
+            .iter()
+            .filter(|(key, _)| !current.contains_key(key))
+            .map(|(key, old)| (key, Change::Gone(old))),
+    );
+    changes.sort_by(|a, b| a.0.cmp(b.0));
+
+    return changes;
+}
+
+/// Reads the answers for `parts` out of a `report --format json` document.
+pub fn snapshot(report: &serde_json::Value, parts: &[Part]) -> Result<Snapshot, Error> {
+    let rows = report.as_array().ok_or("expected a JSON array of rows")?;
+    let mut snapshot = Snapshot::new();
+
+    for row in rows {
+        let part = row["part"]
+            .as_u64()
+            .and_then(|p| u8::try_from(p).ok())
+            .ok_or("expected a part number")?;
+        let part = Part::try_from(part)?;
+        if !parts.contains(&part) {
+            continue;
+        }
+
+        let variant = row["variant"].as_str().ok_or("expected a variant name")?;
+        let answer = match (row["answer"].as_str(), row["error"].as_str()) {
+            (Some(answer), _) => Ok(answer.to_string()),
+            (None, Some(error)) => Err(error.to_string()),
+            (None, None) => Err("no answer".to_string()),
+        };
+        snapshot.insert((part, variant.to_string()), answer);
+    }
+
+    return Ok(snapshot);
+}
+
+fn cargo(subcommand: &str) -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo")));
+    // A target directory of its own, so rebuilding never replaces the binary
+    // doing the watching.
+    command
+        .current_dir(workspace_dir())
+        .arg(subcommand)
+        .arg("--quiet")
+        .arg("--target-dir")
+        .arg(workspace_dir().join("target").join("watch"));
+    return command;
+}
+
+/// Rebuilds the runner and reports on `day` with it, so that source changes
+/// are picked up along with input changes. Build errors go to stderr.
+pub fn run_report(day: &Day, input: Option<&Path>, parts: &[Part]) -> Result<Snapshot, Error> {
+    let mut command = cargo("run");
+    command.args(["--release", "-p", "aoc", "--", "report"]);
+    command
+        .arg(day.number.to_string())
+        .args(["--format", "json"]);
+    if let Some(path) = input {
+        command.arg("--input").arg(path);
+    }
+
+    let output = command.stderr(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err("the runner did not build or failed".into());
+    }
+
+    return snapshot(&serde_json::from_slice(&output.stdout)?, parts);
+}
+
+/// Runs the day crate's tests, leaving their output on the terminal.
+pub fn run_tests(day: &Day) -> Result<bool, Error> {
+    let status = cargo("test")
+        .arg("-p")
+        .arg(format!("day{:02}", day.number))
+        .status()?;
+    return Ok(status.success());
+}
+
+/// What to watch for `day`: its crate, the shared library and an input file
+/// given on the command line. Directories are watched recursively.
+pub fn watched_paths(day: &Day, input: Option<&Path>) -> Vec<PathBuf> {
+    let mut paths = vec![day.dir(), workspace_dir().join("aoc-common")];
+    // Watching the file's directory rather than the file survives editors
+    // that save by replacing it.
+    if let Some(dir) = input.and_then(Path::parent) {
+        paths.push(dir.to_path_buf());
+    }
+
+    return paths;
+}
+
+/// Whether a change to `path` should trigger a run: anything but build output
+/// and editor scratch files inside the watched crates, and only the input
+/// itself inside its directory.
+pub fn is_relevant(path: &Path, day: &Day, input: Option<&Path>) -> bool {
+    if input == Some(path) {
+        return true;
+    }
+
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.starts_with('.') || name.ends_with('~') || name.ends_with(".swp") {
+        return false;
+    }
+    let in_target = path
+        .strip_prefix(workspace_dir())
+        .is_ok_and(|rest| rest.components().any(|c| c.as_os_str() == "target"));
+    if in_target {
+        return false;
+    }
+
+    return path.starts_with(day.dir()) || path.starts_with(workspace_dir().join("aoc-common"));
+}
+
+fn collect(
+    event: notify::Result<Event>,
+    relevant: &impl Fn(&Path) -> bool,
+    changed: &mut Vec<PathBuf>,
+) -> Result<(), Error> {
+    let event = event?;
+    if matches!(event.kind, EventKind::Access(_)) {
+        return Ok(());
+    }
+    for path in event.paths {
+        if relevant(&path) && !changed.contains(&path) {
+            changed.push(path);
+        }
+    }
+    return Ok(());
+}
+
+/// Blocks until a relevant change arrives and then until changes stop for a
+/// moment, returning every relevant path that changed.
+pub fn wait_for_change(
+    events: &Receiver<notify::Result<Event>>,
+    relevant: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut changed = Vec::new();
+    while changed.is_empty() {
+        collect(events.recv()?, &relevant, &mut changed)?;
+    }
+    loop {
+        match events.recv_timeout(SETTLE) {
+            Ok(event) => collect(event, &relevant, &mut changed)?,
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => return Err("stopped watching".into()),
+        }
+    }
+
+    return Ok(changed);
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::days;
+
+    fn answers(entries: &[(Part, &str, Result<&str, &str>)]) -> Snapshot {
+        return entries
+            .iter()
+            .map(|(part, variant, answer)| {
+                let answer = answer.map(str::to_string).map_err(str::to_string);
+                return ((*part, variant.to_string()), answer);
+            })
+            .collect();
+    }
+
+    #[test]
+    fn snapshot_reads_selected_parts() {
+        let report = json!([
+            { "part": 1, "variant": "solution", "answer": "24000", "error": null },
+            { "part": 2, "variant": "solution", "answer": null, "error": "no elves" },
+        ]);
+
+        assert_eq!(
+            answers(&[(Part::Two, "solution", Err("no elves"))]),
+            snapshot(&report, &[Part::Two]).unwrap()
+        );
+        assert!(snapshot(&json!({}), &Part::ALL).is_err());
+    }
+
+    #[test]
+    fn diff_reports_each_kind_of_change() {
+        let previous = answers(&[
+            (Part::One, "bitwise", Ok("1")),
+            (Part::One, "iterator", Ok("2")),
+            (Part::Two, "iterator", Ok("3")),
+        ]);
+        let current = answers(&[
+            (Part::One, "iterator", Ok("2")),
+            (Part::Two, "iterator", Err("overflow")),
+            (Part::Two, "stream", Ok("3")),
+        ]);
+
+        let kinds: Vec<Change> = diff(&previous, &current)
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(
+            vec![
+                Change::Gone(&Ok("1".to_string())),
+                Change::Same(&Ok("2".to_string())),
+                Change::Changed(&Ok("3".to_string()), &Err("overflow".to_string())),
+                Change::New(&Ok("3".to_string())),
+            ],
+            kinds
+        );
+    }
+
+    #[test]
+    fn is_relevant_skips_build_output_and_scratch_files() {
+        let day = days::find(1).unwrap();
+        let input = Path::new("/tmp/elves.txt");
+
+        assert!(is_relevant(&day.dir().join("src/lib.rs"), day, Some(input)));
+        assert!(is_relevant(input, day, Some(input)));
+        assert!(!is_relevant(Path::new("/tmp/other.txt"), day, Some(input)));
+        assert!(!is_relevant(&day.dir().join("src/.lib.rs.swp"), day, None));
+        assert!(!is_relevant(
+            &workspace_dir().join("target/debug/aoc"),
+            day,
+            None
+        ));
+        assert!(!is_relevant(
+            &days::find(2).unwrap().dir().join("src/lib.rs"),
+            day,
+            None
+        ));
+    }
+}
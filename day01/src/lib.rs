@@ -1,172 +1,429 @@
-pub fn part_one(input: &str) -> i32 {
-    let contents: Vec<&str> = input.split('\n').collect();
-    return contents
-        .split(|&i| i == "")
-        .map(|elf| elf.iter().map(|x| x.parse::<i32>().unwrap()).sum::<i32>())
-        .max()
-        .unwrap();
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::io::{BufRead, Write};
+
+use alloc::vec::Vec;
+use aoc_common::{
+    aoc,
+    parse::{blocks, unsigned},
+    Error, ParseError, Solution,
+};
+#[cfg(feature = "std")]
+use aoc_common::{Answers, Generator, Part, Rng, Streamer};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    #[cfg(feature = "std")]
+    const STREAM: Option<Streamer> = Some(|reader, part| {
+        return match part {
+            Part::One => Ok(part_one_stream(reader)?.to_string()),
+            Part::Two => Ok(part_two_stream(reader)?.to_string()),
+        };
+    });
+    #[cfg(feature = "std")]
+    const GENERATE: Option<Generator> = Some(generate);
+
+    type Input<'a> = Vec<i32>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return blocks(input)
+            .map(|elf| {
+                return elf.lines().try_fold(0i32, |sum, x| {
+                    return sum
+                        .checked_add(parse_calories(input, x)?)
+                        .ok_or_else(|| ParseError::at(input, elf, "too many calories"));
+                });
+            })
+            .collect();
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return input
+            .iter()
+            .copied()
+            .max()
+            .ok_or("no elves in input".into());
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        let mut sums = input.clone();
+        sums.sort();
+        return total(&sums[sums.len().saturating_sub(3)..]).ok_or("too many calories".into());
+    }
+}
+
+fn parse_calories(input: &str, line: &str) -> Result<i32, ParseError> {
+    return unsigned(input, line);
+}
+
+// Reports the block of the elf whose line starts at `at` once its calories no
+// longer fit in an `i32`, the same block `Day01::parse` reports.
+fn too_many_calories(input: &str, at: usize) -> ParseError {
+    let start = input[..at].rfind("\n\n").map_or(0, |i| i + 2);
+    let elf = blocks(&input[start..]).next().unwrap_or(&input[start..]);
+    return ParseError::at(input, elf, "too many calories");
+}
+
+fn total(calories: &[i32]) -> Option<i32> {
+    return calories.iter().try_fold(0i32, |sum, c| sum.checked_add(*c));
 }
 
-pub fn part_one_imperative(input: &str) -> i32 {
+#[aoc(day = 1, part = 1, variant = "imperative")]
+pub fn part_one_imperative(input: &str) -> Result<i32, ParseError> {
     let contents: Vec<&str> = input.split('\n').collect();
 
     let mut max_calories: i32 = 0;
     let mut elf_sum: i32 = 0;
 
     for line in contents {
-        if line == "" {
+        if line.is_empty() {
             if elf_sum > max_calories {
                 max_calories = elf_sum;
             }
             elf_sum = 0;
         } else {
-            elf_sum += line.parse::<i32>().unwrap();
+            let at = line.as_ptr() as usize - input.as_ptr() as usize;
+            elf_sum = elf_sum
+                .checked_add(parse_calories(input, line)?)
+                .ok_or_else(|| too_many_calories(input, at))?;
+        }
+    }
+
+    return Ok(max_calories.max(elf_sum));
+}
+
+// Adds the digit at `i` to `current`, the calories read so far from the line
+// starting at `start`. Anything that is not a digit, or overflows, is handed
+// to `parse::unsigned` so the error matches the other variants'.
+fn push_digit(input: &str, start: usize, i: usize, current: i32) -> Result<i32, ParseError> {
+    let byte = input.as_bytes()[i];
+    if byte.is_ascii_digit() {
+        if let Some(next) = current
+            .checked_mul(10)
+            .and_then(|c| c.checked_add((byte - b'0') as i32))
+        {
+            return Ok(next);
         }
     }
 
-    return max_calories;
+    let line = input[start..].split('\n').next().unwrap_or_default();
+    return Err(unsigned::<i32>(input, line)
+        .err()
+        .unwrap_or_else(|| ParseError::at(input, line, "expected a number")));
 }
 
-pub fn part_one_bitwise(input: &str) -> i32 {
-    let bytes = input.as_bytes();
+#[aoc(day = 1, part = 1, variant = "bitwise")]
+pub fn part_one_bitwise(input: &str) -> Result<i32, ParseError> {
     let mut max: i32 = 0;
     let mut sum: i32 = 0;
     let mut current: i32 = 0;
+    let mut start = 0;
 
-    for byte in bytes {
-        if *byte != b'\n' {
-            current *= 10;
-            current += (byte - b'0') as i32;
-        } else if current == 0 && sum > max {
-            max = sum;
-            sum = 0;
-        } else if current == 0 {
+    for (i, byte) in input.bytes().enumerate() {
+        if byte != b'\n' {
+            current = push_digit(input, start, i, current)?;
+            continue;
+        }
+
+        if i == start {
+            max = max.max(sum);
             sum = 0;
         } else {
-            sum += current;
+            sum = sum
+                .checked_add(current)
+                .ok_or_else(|| too_many_calories(input, start))?;
             current = 0;
         }
+        start = i + 1;
     }
 
-    return max;
+    let sum = sum
+        .checked_add(current)
+        .ok_or_else(|| too_many_calories(input, start))?;
+    return Ok(max.max(sum));
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let contents: Vec<&str> = input.split('\n').collect();
-    let mut sums: Vec<i32> = contents
-        .split(|&i| i == "")
-        .map(|elf| elf.iter().map(|x| x.parse::<i32>().unwrap()).sum::<i32>())
-        .collect();
-
-    sums.sort();
-    return sums.iter().rev().take(3).sum();
-}
-
-pub fn part_two_imperative(input: &str) -> i32 {
+#[aoc(day = 1, part = 2, variant = "imperative")]
+pub fn part_two_imperative(input: &str) -> Result<i32, ParseError> {
     let contents: Vec<&str> = input.split('\n').collect();
 
     let mut top_three_calories: [i32; 3] = [0; 3];
     let mut elf_sum: i32 = 0;
 
     for line in contents {
-        if line == "" {
-            let mut shift_right = false;
-            let mut prev_value: i32 = 0;
-
-            for index in 0..top_three_calories.len() {
-                if shift_right {
-                    let temp = top_three_calories[index];
-                    top_three_calories[index] = prev_value;
-                    prev_value = temp;
-                } else if elf_sum > top_three_calories[index] {
-                    shift_right = true;
-                    prev_value = top_three_calories[index];
-                    top_three_calories[index] = elf_sum;
-                }
-            }
-
+        if line.is_empty() {
+            insert_top_three(&mut top_three_calories, elf_sum);
             elf_sum = 0;
         } else {
-            elf_sum += line.parse::<i32>().unwrap();
+            let at = line.as_ptr() as usize - input.as_ptr() as usize;
+            elf_sum = elf_sum
+                .checked_add(parse_calories(input, line)?)
+                .ok_or_else(|| too_many_calories(input, at))?;
         }
     }
 
-    return top_three_calories.iter().sum();
+    insert_top_three(&mut top_three_calories, elf_sum);
+    return total(&top_three_calories)
+        .ok_or_else(|| ParseError::at_end(input, "too many calories"));
 }
 
-pub fn part_two_bitwise(input: &str) -> i32 {
-    let bytes = input.as_bytes();
+#[aoc(day = 1, part = 2, variant = "bitwise")]
+pub fn part_two_bitwise(input: &str) -> Result<i32, ParseError> {
     let mut top_three_calories: [i32; 3] = [0; 3];
     let mut sum: i32 = 0;
     let mut current: i32 = 0;
+    let mut start = 0;
 
-    for byte in bytes {
-        if *byte != b'\n' {
-            current *= 10;
-            current += (byte - b'0') as i32;
-        } else if current == 0 {
-            let mut shift_right = false;
-            let mut prev_value: i32 = 0;
-
-            for index in 0..top_three_calories.len() {
-                if shift_right {
-                    let temp = top_three_calories[index];
-                    top_three_calories[index] = prev_value;
-                    prev_value = temp;
-                } else if sum > top_three_calories[index] {
-                    shift_right = true;
-                    prev_value = top_three_calories[index];
-                    top_three_calories[index] = sum;
-                }
-            }
+    for (i, byte) in input.bytes().enumerate() {
+        if byte != b'\n' {
+            current = push_digit(input, start, i, current)?;
+            continue;
+        }
 
+        if i == start {
+            insert_top_three(&mut top_three_calories, sum);
             sum = 0;
         } else {
-            sum += current;
+            sum = sum
+                .checked_add(current)
+                .ok_or_else(|| too_many_calories(input, start))?;
             current = 0;
         }
+        start = i + 1;
     }
 
-    return top_three_calories[0] + top_three_calories[1] + top_three_calories[2];
+    let sum = sum
+        .checked_add(current)
+        .ok_or_else(|| too_many_calories(input, start))?;
+    insert_top_three(&mut top_three_calories, sum);
+    return total(&top_three_calories)
+        .ok_or_else(|| ParseError::at_end(input, "too many calories"));
+}
+
+// Hands each elf's total to `elf` as soon as its blank line is read, keeping
+// only the current line in memory.
+#[cfg(feature = "std")]
+fn stream_elves(mut reader: impl BufRead, mut elf: impl FnMut(i32)) -> Result<(), Error> {
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut sum: i32 = 0;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let calories = line.trim_end_matches(['\n', '\r']);
+        if calories.is_empty() {
+            elf(sum);
+            sum = 0;
+        } else {
+            let calories: i32 = unsigned(calories, calories).map_err(|mut e| {
+                e.line = line_number;
+                return e;
+            })?;
+            sum = sum.checked_add(calories).ok_or("too many calories")?;
+        }
+    }
+
+    elf(sum);
+    return Ok(());
+}
+
+#[cfg(feature = "std")]
+pub fn part_one_stream(reader: impl BufRead) -> Result<i32, Error> {
+    let mut max_calories: i32 = 0;
+    stream_elves(reader, |sum| max_calories = max_calories.max(sum))?;
+    return Ok(max_calories);
+}
+
+#[cfg(feature = "std")]
+pub fn part_two_stream(reader: impl BufRead) -> Result<i32, Error> {
+    let mut top_three_calories: [i32; 3] = [0; 3];
+    stream_elves(reader, |sum| insert_top_three(&mut top_three_calories, sum))?;
+    return total(&top_three_calories).ok_or("too many calories".into());
+}
+
+#[cfg(feature = "std")]
+#[aoc(day = 1, part = 1, variant = "stream")]
+fn stream_part_one(input: &str) -> Result<i32, Error> {
+    return part_one_stream(input.as_bytes());
+}
+
+#[cfg(feature = "std")]
+#[aoc(day = 1, part = 2, variant = "stream")]
+fn stream_part_two(input: &str) -> Result<i32, Error> {
+    return part_two_stream(input.as_bytes());
+}
+
+/// Writes `size` elves carrying one to ten snacks each.
+#[cfg(feature = "std")]
+pub fn generate(size: usize, rng: &mut Rng, out: &mut dyn Write) -> Result<Answers, Error> {
+    let mut top_three_calories: [i32; 3] = [0; 3];
+
+    for elf in 0..size {
+        if elf > 0 {
+            writeln!(out)?;
+        }
+        let mut sum: i32 = 0;
+        for _ in 0..rng.range(1..11) {
+            let calories = rng.range(1..100_000) as i32;
+            writeln!(out, "{}", calories)?;
+            sum += calories;
+        }
+        insert_top_three(&mut top_three_calories, sum);
+    }
+
+    return Ok(Answers::new(
+        top_three_calories[0],
+        top_three_calories.iter().sum::<i32>(),
+    ));
+}
+
+fn insert_top_three(top_three_calories: &mut [i32; 3], sum: i32) {
+    let mut shift_right = false;
+    let mut prev_value: i32 = 0;
+
+    for calories in top_three_calories.iter_mut() {
+        if shift_right {
+            core::mem::swap(calories, &mut prev_value);
+        } else if sum > *calories {
+            shift_right = true;
+            prev_value = *calories;
+            *calories = sum;
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    fn elves() -> impl Strategy<Value = String> {
+        let elf = vec(1..100_000i32, 1..10);
+        return (vec(elf, 1..20), any::<bool>()).prop_map(|(elves, trailing_newline)| {
+            let mut input = elves
+                .iter()
+                .map(|elf| {
+                    elf.iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if trailing_newline {
+                input.push('\n');
+            }
+            return input;
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day01::variants()).unwrap();
     }
 
     #[test]
     fn part_one_returns_correct_result() {
-        assert_eq!(71924, part_one(&get_input()));
+        let answer = cross_check(&Day01::variants(), Part::One, Day01::INPUT).unwrap();
+        assert_eq!("71924", answer);
     }
 
     #[test]
-    fn part_one_imperative_returns_correct_result() {
-        assert_eq!(71924, part_one_imperative(&get_input()));
+    fn part_two_returns_correct_result() {
+        let answer = cross_check(&Day01::variants(), Part::Two, Day01::INPUT).unwrap();
+        assert_eq!("210406", answer);
     }
 
     #[test]
-    fn part_one_bitwise_returns_correct_result() {
-        assert_eq!(71924, part_one_bitwise(&get_input()));
+    fn parse_reports_invalid_calories() {
+        let err = Day01::parse("1000\n2000\n\n30x0\n").unwrap_err();
+        assert_eq!((4, 3, "x"), (err.line, err.column, err.text.as_str()));
+        assert_eq!(
+            Err(err.clone()),
+            part_one_imperative("1000\n2000\n\n30x0\n")
+        );
+        assert_eq!(Err(err), part_one_bitwise("1000\n2000\n\n30x0\n"));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn part_two_returns_correct_result() {
-        assert_eq!(210406, part_two(&get_input()));
+    fn stream_reports_invalid_calories() {
+        let err = Day01::parse("1000\n2000\n\n30x0\n").unwrap_err();
+        let streamed = part_one_stream("1000\n2000\n\n30x0\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), streamed.to_string());
     }
 
     #[test]
-    fn part_two_imperative_returns_correct_result() {
-        assert_eq!(210406, part_two_imperative(&get_input()));
+    fn every_variant_rejects_too_many_calories() {
+        let input = "1\n\n2147483647\n1\n";
+        let err = Day01::parse(input).unwrap_err();
+        assert_eq!(
+            (3, 1, "2147483647\n1", "too many calories"),
+            (
+                err.line,
+                err.column,
+                err.text.as_str(),
+                err.message.as_str()
+            )
+        );
+        assert_eq!(Err(err.clone()), part_one_imperative(input));
+        assert_eq!(Err(err.clone()), part_one_bitwise(input));
+        assert_eq!(Err(err), part_two_bitwise(input));
+        for variant in Day01::variants() {
+            assert!((variant.solve)(input).is_err(), "{}", variant.name);
+        }
+
+        let input = "2000000000\n\n2000000000\n";
+        for variant in Day01::variants() {
+            let answer = (variant.solve)(input);
+            assert_eq!(
+                variant.part == Part::Two,
+                answer.is_err(),
+                "{}",
+                variant.name
+            );
+        }
     }
 
     #[test]
-    fn part_two_bitwise_returns_correct_result() {
-        assert_eq!(210406, part_two_bitwise(&get_input()));
+    fn bitwise_rejects_what_parse_rejects() {
+        for input in ["A Z\n", "x\n", "1\n\n99999999999\n"] {
+            let err = Day01::parse(input).unwrap_err();
+            assert_eq!(Err(err.clone()), part_one_bitwise(input));
+            assert_eq!(Err(err), part_two_bitwise(input));
+        }
+        assert_eq!(Ok(11), part_one_bitwise("5\n0\n6\n\n7"));
+    }
+
+    proptest! {
+        #[test]
+        fn variants_agree_on_generated_input(input in elves()) {
+            for part in Part::ALL {
+                cross_check(&Day01::variants(), part, &input)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+            }
+        }
     }
 }
@@ -1,5 +1,13 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use aoc_common::{aoc, parse::fields, Error, ParseError, Solution};
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-enum Hand {
+pub enum Hand {
     Rock,
     Paper,
     Scissors,
@@ -70,101 +78,156 @@ fn get_hand_from_result(theirs: Hand, result: MatchResult) -> Hand {
     };
 }
 
+fn parse_game(input: &str, game: &str) -> Result<(Hand, char), ParseError> {
+    let [theirs, mine] = fields(input, game, &[' '])?;
+    let hand = match theirs {
+        "A" | "B" | "C" => theirs.chars().next().and_then(map_hand),
+        _ => None,
+    }
+    .ok_or_else(|| ParseError::at(input, theirs, "expected A, B or C"))?;
+    let mine = match mine {
+        "X" | "Y" | "Z" => mine.chars().next(),
+        _ => None,
+    }
+    .ok_or_else(|| ParseError::at(input, mine, "expected X, Y or Z"))?;
+
+    return Ok((hand, mine));
+}
+
 fn score_game(choice: Hand, result: MatchResult) -> i32 {
     return choice as i32 + 1 + result as i32 * 3;
 }
 
-pub fn part_one(input: &str) -> i32 {
-    return input.lines().filter(|x| x.len() > 0).fold(0, |res, game| {
-        let hands: Vec<Hand> = game.chars().filter_map(map_hand).collect();
-        return res + score_game(hands[1], determine_winner(hands[1], hands[0]));
-    });
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+
+    type Input<'a> = Vec<(Hand, char)>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return input
+            .lines()
+            .filter(|x| !x.is_empty())
+            .map(|game| parse_game(input, game))
+            .collect();
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return input.iter().try_fold(0, |res, &(theirs, mine)| {
+            let mine = map_hand(mine).ok_or("invalid hand")?;
+            return Ok(res + score_game(mine, determine_winner(mine, theirs)));
+        });
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return input.iter().try_fold(0, |res, &(theirs, result)| {
+            let result = get_desired_result(result).ok_or("invalid result")?;
+            let mine = get_hand_from_result(theirs, result);
+            return Ok(res + score_game(mine, result));
+        });
+    }
 }
 
-pub fn part_one_imperative(input: &str) -> i32 {
+#[aoc(day = 2, part = 1, variant = "imperative")]
+pub fn part_one_imperative(input: &str) -> Result<i32, ParseError> {
     let games: Vec<&str> = input.split('\n').collect();
     let mut score: i32 = 0;
 
     for game in games {
-        if game.len() != 3 {
+        if game.is_empty() {
             continue;
         }
 
-        let their_hand = map_hand(game.chars().nth(0).unwrap()).unwrap();
-        let my_hand = map_hand(game.chars().nth(2).unwrap()).unwrap();
+        let (their_hand, mine) = parse_game(input, game)?;
+        let my_hand = map_hand(mine).ok_or_else(|| ParseError::at(input, game, "invalid hand"))?;
         let result = determine_winner(my_hand, their_hand);
 
         score += score_game(my_hand, result);
     }
 
-    return score;
+    return Ok(score);
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let games: Vec<char> = input
-        .lines()
-        .filter(|i| i.len() > 0)
-        .flat_map(|s| s.chars().filter(|&c| c != ' '))
-        .collect();
-
-    return games
-        .iter()
-        .step_by(2)
-        .zip(games.iter().skip(1).step_by(2))
-        .fold(0, |res, (&their_choice, &my_result)| {
-            let theirs = map_hand(their_choice).unwrap();
-            let result = get_desired_result(my_result).unwrap();
-            let mine = get_hand_from_result(theirs, result);
-            return res + score_game(mine, result);
-        });
-}
-
-pub fn part_two_imperative(input: &str) -> i32 {
+#[aoc(day = 2, part = 2, variant = "imperative")]
+pub fn part_two_imperative(input: &str) -> Result<i32, ParseError> {
     let games: Vec<&str> = input.split('\n').collect();
     let mut score: i32 = 0;
 
     for game in games {
-        if game.len() != 3 {
+        if game.is_empty() {
             continue;
         }
 
-        let their_hand = map_hand(game.chars().nth(0).unwrap()).unwrap();
-        let result = get_desired_result(game.chars().nth(2).unwrap()).unwrap();
+        let (their_hand, result) = parse_game(input, game)?;
+        let result = get_desired_result(result)
+            .ok_or_else(|| ParseError::at(input, game, "invalid result"))?;
         let my_hand = get_hand_from_result(their_hand, result);
 
         score += score_game(my_hand, result);
     }
 
-    return score;
+    return Ok(score);
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use alloc::{
+        format,
+        string::{String, ToString},
+    };
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
+    fn strategy_guide() -> impl Strategy<Value = String> {
+        let game = ("[ABC]", "[XYZ]").prop_map(|(theirs, mine)| format!("{} {}", theirs, mine));
+        return (vec(game, 1..50), any::<bool>()).prop_map(|(games, trailing_newline)| {
+            let mut input = games.join("\n");
+            if trailing_newline {
+                input.push('\n');
+            }
+            return input;
+        });
+    }
+
+    #[cfg(feature = "std")]
     #[test]
-    fn part_one_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(14264, part_one(input.as_str()));
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day02::variants()).unwrap();
     }
 
     #[test]
-    fn part_one_imperative_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(14264, part_one_imperative(input.as_str()));
+    fn part_one_returns_correct_result() {
+        let answer = cross_check(&Day02::variants(), Part::One, Day02::INPUT).unwrap();
+        assert_eq!("14264", answer);
     }
 
     #[test]
     fn part_two_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(12382, part_two(input.as_str()));
+        let answer = cross_check(&Day02::variants(), Part::Two, Day02::INPUT).unwrap();
+        assert_eq!("12382", answer);
     }
 
     #[test]
-    fn part_two_imperative_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(12382, part_two_imperative(input.as_str()));
+    fn parse_reports_invalid_hand() {
+        let err = Day02::parse("A Y\nB X\nD Z\n").unwrap_err();
+        assert_eq!("line 3, column 1: expected A, B or C: `D`", err.to_string());
+    }
+
+    proptest! {
+        #[test]
+        fn variants_agree_on_generated_input(input in strategy_guide()) {
+            for part in Part::ALL {
+                cross_check(&Day02::variants(), part, &input)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+            }
+        }
     }
 }
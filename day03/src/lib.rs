@@ -1,65 +1,143 @@
-use std::collections::HashSet;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn part_one(input: &str) -> i32 {
-    return input
-        .lines()
-        .filter(|x| x.len() > 0)
-        .map(|ruck| ruck.split_at(ruck.len() / 2))
-        .map(|(comp_a, comp_b)| {
-            *comp_a
-                .chars()
-                .collect::<HashSet<_>>()
-                .intersection(&comp_b.chars().collect::<HashSet<_>>())
-                .nth(0)
-                .unwrap() as u32
-        })
-        .fold(0, |res, uniq| {
-            return match uniq {
-                // A-Z (uppercase)
-                65..=90 => res + uniq - 65 + 27,
-                // a-z (lowercase)
-                97..=122 => res + uniq - 97 + 1,
-                _ => res,
-            };
-        }) as i32;
+extern crate alloc;
+
+use alloc::{collections::BTreeSet, format, vec::Vec};
+
+use aoc_common::{aoc, Error, ParseError, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+
+    type Input<'a> = Vec<&'a str>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return input
+            .lines()
+            .filter(|x| !x.is_empty())
+            .map(|ruck| rucksack(input, ruck))
+            .collect();
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return input
+            .iter()
+            .map(|ruck| ruck.split_at(ruck.len() / 2))
+            .map(|(comp_a, comp_b)| {
+                let uniq = *comp_a
+                    .chars()
+                    .collect::<BTreeSet<_>>()
+                    .intersection(&comp_b.chars().collect::<BTreeSet<_>>())
+                    .next()
+                    .ok_or("no item shared between compartments")?;
+                return priority(uniq);
+            })
+            .sum();
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return input
+            .chunks(3)
+            .map(|group| {
+                let badge = group
+                    .iter()
+                    .map(|ruck| ruck.chars().collect::<BTreeSet<_>>())
+                    .reduce(|res, ruck| res.intersection(&ruck).copied().collect())
+                    .and_then(|id| id.into_iter().next())
+                    .ok_or("no item shared within group")?;
+                return priority(badge);
+            })
+            .sum();
+    }
 }
 
-pub fn part_one_imperative(input: &str) -> i32 {
-    let rucksacks: Vec<&str> = input.split('\n').collect();
+fn rucksack<'a>(input: &str, ruck: &'a str) -> Result<&'a str, ParseError> {
+    if let Some((i, c)) = ruck.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        let item = &ruck[i..i + c.len_utf8()];
+        return Err(ParseError::at(input, item, "invalid item"));
+    }
+    if !ruck.len().is_multiple_of(2) {
+        return Err(ParseError::at(input, ruck, "odd number of items"));
+    }
+    return Ok(ruck);
+}
+
+// The variants check items as they go and hand a rucksack they find wrong to
+// `rucksack`, so the error matches the one `Day03::parse` gives.
+fn invalid_rucksack(input: &str, ruck: &str) -> ParseError {
+    return rucksack(input, ruck)
+        .err()
+        .unwrap_or_else(|| ParseError::at(input, ruck, "invalid rucksack"));
+}
+
+fn priority(item: char) -> Result<u32, Error> {
+    let decimal_utf8 = item as u32;
+    return match decimal_utf8 {
+        // A-Z (uppercase)
+        65..=90 => Ok(decimal_utf8 - 65 + 27),
+        // a-z (lowercase)
+        97..=122 => Ok(decimal_utf8 - 97 + 1),
+        _ => Err(format!("invalid item `{}`", item).into()),
+    };
+}
+
+#[aoc(day = 3, part = 1, variant = "imperative")]
+pub fn part_one_imperative(input: &str) -> Result<i32, ParseError> {
     let mut sum: u32 = 0;
-    let mut uniq_a: HashSet<char> = HashSet::new();
-    let mut uniq_b: HashSet<char> = HashSet::new();
+    let mut uniq_a: BTreeSet<char> = BTreeSet::new();
+    let mut uniq_b: BTreeSet<char> = BTreeSet::new();
 
-    for ruck in rucksacks {
-        if ruck.len() == 0 {
+    for ruck in input.lines() {
+        if ruck.is_empty() {
             continue;
         }
+        if !ruck.len().is_multiple_of(2) || !ruck.is_ascii() {
+            return Err(invalid_rucksack(input, ruck));
+        }
 
         uniq_a.clear();
         uniq_b.clear();
 
         let (comp_a, comp_b) = ruck.split_at(ruck.len() / 2);
         for a in comp_a.chars() {
+            if !a.is_ascii_alphabetic() {
+                return Err(invalid_rucksack(input, ruck));
+            }
             uniq_a.insert(a);
         }
         for b in comp_b.chars() {
+            if !b.is_ascii_alphabetic() {
+                return Err(invalid_rucksack(input, ruck));
+            }
             uniq_b.insert(b);
         }
 
-        let uniq = *uniq_a.intersection(&uniq_b).nth(0).unwrap() as u32;
+        let uniq = *uniq_a
+            .intersection(&uniq_b)
+            .next()
+            .ok_or_else(|| ParseError::at(input, ruck, "no item shared between compartments"))?
+            as u32;
 
         match uniq {
             // A-Z (uppercase)
             65..=90 => sum += uniq - 65 + 27,
             // a-z (lowercase)
             97..=122 => sum += uniq - 97 + 1,
-            _ => unreachable!(),
+            _ => return Err(ParseError::at(input, ruck, "invalid item")),
         }
     }
 
-    return sum as i32;
+    return Ok(sum as i32);
 }
 
+// Anything but a letter is rejected before it is shifted, so every shift
+// below stays within the filters.
+//
 // As lowercase and uppercase characters represent different priorities,
 // we have 26 + 26 = 52 values individually. Additionally, strings in Rust
 // are UTF-8, which means the byte values correspond with the UTF-8 spec.
@@ -72,87 +150,185 @@ pub fn part_one_imperative(input: &str) -> i32 {
 //
 // As the challenge requires that `a` = 1 and `A` = 27, we add 27 to capital
 // characters, and subtract 31 from everything else.
-pub fn part_one_bitwise(input: &str) -> usize {
+#[aoc(day = 3, part = 1, variant = "bitwise")]
+pub fn part_one_bitwise(input: &str) -> Result<usize, ParseError> {
     return input
         .lines()
-        .map(|ruck| ruck.split_at(ruck.len() / 2))
-        .fold(0, |res, (comp_a, comp_b)| {
+        .filter(|x| !x.is_empty())
+        .try_fold(0, |res, ruck| {
+            let bytes = ruck.as_bytes();
+            if !bytes.len().is_multiple_of(2) {
+                return Err(invalid_rucksack(input, ruck));
+            }
+
+            let (comp_a, comp_b) = bytes.split_at(bytes.len() / 2);
             let mut filter_a: u64 = 0;
             let mut filter_b: u64 = 0;
-            for c in comp_a.as_bytes() {
+            for c in comp_a {
+                if !c.is_ascii_alphabetic() {
+                    return Err(invalid_rucksack(input, ruck));
+                }
                 filter_a |= 1 << (c - b'A');
             }
-            for c in comp_b.as_bytes() {
+            for c in comp_b {
+                if !c.is_ascii_alphabetic() {
+                    return Err(invalid_rucksack(input, ruck));
+                }
                 filter_b |= 1 << (c - b'A');
             }
 
             let matching = filter_a & filter_b;
-            assert_eq!(matching.count_ones(), 1);
+            if matching.count_ones() != 1 {
+                return Err(ParseError::at(
+                    input,
+                    ruck,
+                    "expected one item shared between compartments",
+                ));
+            }
             let zeros = matching.trailing_zeros() as usize;
 
             return match zeros {
-                0..=26 => res + zeros + 27,
-                _ => res + zeros - 31,
-            };
-        });
-}
-
-pub fn part_two(input: &str) -> i32 {
-    let rucksacks: Vec<&str> = input.split('\n').collect();
-    return rucksacks
-        .chunks(3)
-        .map(|group| {
-            return group
-                .iter()
-                .map(|ruck| ruck.chars().collect::<HashSet<_>>())
-                .reduce(|res, ruck| res.intersection(&ruck).map(|c| *c).collect());
-        })
-        .filter_map(|opt| {
-            return match opt {
-                Some(id) => Some(id.iter().map(|c| *c).collect::<Vec<_>>()),
-                _ => None,
-            };
-        })
-        .filter(|x| x.len() > 0)
-        .fold(0, |res, id| {
-            let decimal_utf8 = id[0] as u32;
-            return match decimal_utf8 {
-                // A-Z (uppercase)
-                65..=90 => res + (decimal_utf8 - 65 + 27) as i32,
-                // a-z (lowercase)
-                97..=122 => res + (decimal_utf8 - 97 + 1) as i32,
-                _ => unreachable!(),
+                0..=26 => Ok(res + zeros + 27),
+                _ => Ok(res + zeros - 31),
             };
         });
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use alloc::string::{String, ToString};
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+    use proptest::{
+        collection::vec,
+        prelude::*,
+        sample::{select, Index},
+    };
 
     use super::*;
 
+    const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    // Each rucksack draws from its own pool of items, so the only item shared
+    // between its compartments is `pool[0]` and the only one shared with the
+    // rest of its group is the badge, which sits in the first compartment.
+    fn rucksack(pool: Vec<u8>, badge: u8) -> impl Strategy<Value = String> {
+        return (0..12usize).prop_flat_map(move |len| {
+            let first = vec(select(pool[1..9].to_vec()), len);
+            let second = vec(select(pool[9..].to_vec()), len + 1);
+            let shared = pool[0];
+            return (first, second)
+                .prop_flat_map(move |(mut a, mut b)| {
+                    a.extend([shared, badge]);
+                    b.push(shared);
+                    return (Just(a).prop_shuffle(), Just(b).prop_shuffle());
+                })
+                .prop_map(|(mut a, b)| {
+                    a.extend(b);
+                    return String::from_utf8(a).unwrap();
+                });
+        });
+    }
+
+    fn group() -> impl Strategy<Value = String> {
+        return Just(ITEMS.to_vec())
+            .prop_shuffle()
+            .prop_flat_map(|items| {
+                return (
+                    rucksack(items[1..18].to_vec(), items[0]),
+                    rucksack(items[18..35].to_vec(), items[0]),
+                    rucksack(items[35..].to_vec(), items[0]),
+                );
+            })
+            .prop_map(|(a, b, c)| [a, b, c].join("\n"));
+    }
+
+    // Blank lines, which `Day03::parse` skips, and lines it rejects for a
+    // stray character or an odd number of items.
+    fn noise() -> impl Strategy<Value = String> {
+        return prop_oneof![
+            3 => Just(String::new()),
+            1 => "[a-zA-Z]{0,6}[0-9 -][a-zA-Z]{0,6}",
+            1 => "([a-zA-Z]{2}){0,5}[a-zA-Z]",
+        ];
+    }
+
+    fn rucksacks() -> impl Strategy<Value = String> {
+        return (
+            vec(group(), 1..20),
+            vec((any::<Index>(), noise()), 0..4),
+            any::<bool>(),
+        )
+            .prop_map(|(groups, noise, trailing_newline)| {
+                let mut lines: Vec<&str> = groups.iter().flat_map(|g| g.lines()).collect();
+                for (at, line) in &noise {
+                    lines.insert(at.index(lines.len() + 1), line);
+                }
+                let mut input = lines.join("\n");
+                if trailing_newline {
+                    input.push('\n');
+                }
+                return input;
+            });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day03::variants()).unwrap();
+    }
+
     #[test]
     fn part_one_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(7746, part_one(input.as_str()));
+        let answer = cross_check(&Day03::variants(), Part::One, Day03::INPUT).unwrap();
+        assert_eq!("7746", answer);
     }
 
     #[test]
-    fn part_one_by_char_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(7746, part_one_bitwise(input.as_str()));
+    fn part_two_returns_correct_result() {
+        let answer = cross_check(&Day03::variants(), Part::Two, Day03::INPUT).unwrap();
+        assert_eq!("2604", answer);
     }
 
     #[test]
-    fn part_one_imperative_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(7746, part_one_imperative(input.as_str()));
+    fn parse_reports_invalid_item() {
+        let err = Day03::parse("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGD-LGLrsFMfFZSrLrFZsSL\n")
+            .unwrap_err();
+        assert_eq!((2, 14, "-"), (err.line, err.column, err.text.as_str()));
     }
 
     #[test]
-    fn part_two_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(2604, part_two(input.as_str()));
+    fn variants_reject_what_parse_rejects() {
+        for input in [
+            "0\n",
+            "R 0\n",
+            "1-2,3\n",
+            "aba\n",
+            "a1a2\n",
+            "ab\u{e9}a\n",
+            "a\u{e9}b\n",
+        ] {
+            let err = Day03::parse(input).unwrap_err();
+            assert_eq!(Err(err.clone()), part_one_imperative(input));
+            assert_eq!(Err(err), part_one_bitwise(input));
+        }
+        assert_eq!(Ok(1), part_one_bitwise("\naa\n\n"));
+    }
+
+    proptest! {
+        #[test]
+        fn variants_agree_on_generated_input(input in rucksacks()) {
+            if Day03::parse(&input).is_err() {
+                for variant in Day03::variants() {
+                    prop_assert!((variant.solve)(&input).is_err(), "{} accepted {:?}", variant.name, input);
+                }
+                return Ok(());
+            }
+            for part in Part::ALL {
+                cross_check(&Day03::variants(), part, &input)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+            }
+        }
     }
 }
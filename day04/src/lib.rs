@@ -1,40 +1,64 @@
-use std::cmp::Ordering;
-
-pub fn part_one(input: &str) -> i32 {
-    let assignments: Vec<&str> = input.split('\n').collect();
-    let mut overlap: i32 = 0;
-
-    for assignment in assignments {
-        if assignment.len() == 0 {
-            continue;
-        }
-
-        let pairs: Vec<_> = assignment.split(',').collect();
-        let first_elf: Vec<i32> = pairs
-            .first()
-            .unwrap()
-            .split('-')
-            .map(|x| x.parse().unwrap())
-            .collect();
-        let second_elf: Vec<i32> = pairs
-            .iter()
-            .nth(1)
-            .unwrap()
-            .split('-')
-            .map(|x| x.parse().unwrap())
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{cmp::Ordering, iter};
+
+use aoc_common::{
+    aoc,
+    parse::{fields, unsigned},
+    Error, ParseError, Solution,
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+
+    // [first start, first end, second start, second end]
+    type Input<'a> = Vec<[i32; 4]>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return input
+            .lines()
+            .filter(|x| !x.is_empty())
+            .map(|assignment| {
+                let bounds: [&str; 4] = fields(input, assignment, &[',', '-'])?;
+                let mut ranges = [0; 4];
+                for (range, bound) in ranges.iter_mut().zip(bounds) {
+                    *range = unsigned(input, bound)?;
+                }
+                return Ok(ranges);
+            })
             .collect();
+    }
 
-        if (first_elf[0] <= second_elf[0] && first_elf[1] >= second_elf[1])
-            || (second_elf[0] <= first_elf[0] && second_elf[1] >= first_elf[1])
-        {
-            overlap += 1;
-        }
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return Ok(input
+            .iter()
+            .filter(|[a0, a1, b0, b1]| (a0 <= b0 && a1 >= b1) || (b0 <= a0 && b1 >= a1))
+            .count());
     }
 
-    return overlap;
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return Ok(input
+            .iter()
+            .filter(|[a0, a1, b0, b1]| {
+                (a0 <= b0 && a1 >= b0)
+                    || (a0 <= b1 && a1 >= b1)
+                    || (b0 <= a0 && b1 >= a0)
+                    || (b0 <= a1 && b1 >= a1)
+            })
+            .count());
+    }
 }
 
-pub fn part_one_by_char(input: &str) -> Result<usize, String> {
+#[aoc(day = 4, part = 1, variant = "by_char")]
+pub fn part_one_by_char(input: &str) -> Result<usize, ParseError> {
     return determine_by_char(input, |ranges| {
         let first = ranges[0].cmp(&ranges[2]);
         let second = ranges[1].cmp(&ranges[3]);
@@ -42,7 +66,8 @@ pub fn part_one_by_char(input: &str) -> Result<usize, String> {
     });
 }
 
-pub fn part_two_by_char(input: &str) -> Result<usize, String> {
+#[aoc(day = 4, part = 2, variant = "by_char")]
+pub fn part_two_by_char(input: &str) -> Result<usize, ParseError> {
     return determine_by_char(input, |ranges| {
         let first_a = ranges[0].cmp(&ranges[2]);
         let first_b = ranges[0].cmp(&ranges[3]);
@@ -59,32 +84,45 @@ pub fn part_two_by_char(input: &str) -> Result<usize, String> {
     });
 }
 
-fn determine_by_char<F>(input: &str, is_overlap: F) -> Result<usize, String>
+fn determine_by_char<F>(input: &str, is_overlap: F) -> Result<usize, ParseError>
 where
-    F: Fn(&[usize; 4]) -> bool,
+    F: Fn(&[i32; 4]) -> bool,
 {
     let mut overlaps: usize = 0;
     let mut indicies: [usize; 8] = [0; 8];
-    let mut ranges: [usize; 4] = [0; 4];
+    let mut ranges: [i32; 4] = [0; 4];
     let mut idx: usize = 0;
 
-    for (i, c) in input.chars().enumerate() {
-        if idx == 0 {
+    let input = input.trim_end_matches('\n');
+    let line_start = |i: usize| input[..i].rfind('\n').map_or(0, |n| n + 1);
+    for (i, c) in input.char_indices().chain(iter::once((input.len(), '\n'))) {
+        if idx == 0 && c == '\n' {
+            // Blank lines, which `Day04::parse` skips too.
+            continue;
+        } else if idx == 0 {
             indicies[idx] = i;
             idx += 1;
         } else if c == '-' || c == ',' {
+            if idx == indicies.len() - 1 {
+                return Err(ParseError::at(
+                    input,
+                    &input[i..i + 1],
+                    "unexpected separator",
+                ));
+            }
             indicies[idx] = i;
             indicies[idx + 1] = i + 1;
             idx += 2;
         } else if c == '\n' {
+            if idx != indicies.len() - 1 {
+                let line = &input[line_start(i)..i];
+                return Err(ParseError::at(input, line, "missing section bound"));
+            }
             indicies[idx] = i;
 
             for j in (0..indicies.len()).step_by(2) {
-                ranges[j / 2] = input
-                    .get(indicies[j]..indicies[j + 1])
-                    .ok_or("Could not retrieve range")?
-                    .parse()
-                    .map_err(|_| "Could not parse number")?;
+                let bound = &input[indicies[j]..indicies[j + 1]];
+                ranges[j / 2] = unsigned(input, bound)?;
             }
 
             if is_overlap(&ranges) {
@@ -98,69 +136,91 @@ where
     return Ok(overlaps);
 }
 
-pub fn part_two(input: &str) -> i32 {
-    let assignments: Vec<&str> = input.split('\n').collect();
-    let mut overlap: i32 = 0;
-
-    for assignment in assignments {
-        if assignment.len() == 0 {
-            continue;
-        }
+#[cfg(test)]
+mod test {
+    use alloc::{
+        format,
+        string::{String, ToString},
+    };
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+    use proptest::{collection::vec, prelude::*};
 
-        let pairs: Vec<_> = assignment.split(',').collect();
-        let first_elf: Vec<i32> = pairs
-            .first()
-            .unwrap()
-            .split('-')
-            .map(|x| x.parse().unwrap())
-            .collect();
-        let second_elf: Vec<i32> = pairs
-            .iter()
-            .nth(1)
-            .unwrap()
-            .split('-')
-            .map(|x| x.parse().unwrap())
-            .collect();
+    use super::*;
 
-        if (first_elf[0] <= second_elf[0] && first_elf[1] >= second_elf[0])
-            || (first_elf[0] <= second_elf[1] && first_elf[1] >= second_elf[1])
-            || ((second_elf[0] <= first_elf[0] && second_elf[1] >= first_elf[0])
-                || second_elf[0] <= first_elf[1] && second_elf[1] >= first_elf[1])
-        {
-            overlap += 1;
-        }
+    fn section(max: u32) -> impl Strategy<Value = (u32, u32)> {
+        return (1..max, 0..max).prop_map(move |(start, len)| (start, (start + len).min(max)));
     }
 
-    return overlap;
-}
-
-#[cfg(test)]
-mod test {
-    use std::fs;
+    // Mostly pairs, with blank lines, which `Day04::parse` skips, and the odd
+    // line of bounds and separators it may well reject.
+    fn assignments() -> impl Strategy<Value = String> {
+        let pair = (section(99), section(99))
+            .prop_map(|((a0, a1), (b0, b1))| format!("{}-{},{}-{}", a0, a1, b0, b1));
+        let line = prop_oneof![
+            20 => pair,
+            2 => Just(String::new()),
+            1 => "[0-9,x -]{0,12}",
+        ];
+        return (vec(line, 1..50), any::<bool>()).prop_map(|(pairs, trailing_newline)| {
+            let mut input = pairs.join("\n");
+            if trailing_newline {
+                input.push('\n');
+            }
+            return input;
+        });
+    }
 
-    use super::*;
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day04::variants()).unwrap();
+    }
 
     #[test]
     fn part_one_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(511, part_one(input.as_str()));
+        let answer = cross_check(&Day04::variants(), Part::One, Day04::INPUT).unwrap();
+        assert_eq!("511", answer);
     }
 
     #[test]
-    fn part_one_by_char_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(Ok(511), part_one_by_char(input.as_str()));
+    fn part_two_returns_correct_result() {
+        let answer = cross_check(&Day04::variants(), Part::Two, Day04::INPUT).unwrap();
+        assert_eq!("821", answer);
     }
 
     #[test]
-    fn part_two_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(821, part_two(input.as_str()));
+    fn parse_reports_invalid_bound() {
+        let input = "2-4,6-8\n2-3,4-x\n";
+        let err = Day04::parse(input).unwrap_err();
+        assert_eq!("line 2, column 7: expected a digit: `x`", err.to_string());
+        assert_eq!(Err(err), part_one_by_char(input));
     }
 
     #[test]
-    fn part_two_by_char_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!(Ok(821), part_two_by_char(input.as_str()));
+    fn by_char_reads_lines_like_parse() {
+        assert_eq!(Ok(2), part_one_by_char("\n2-8,3-7\n\n6-6,4-6\n"));
+        let input = "2-4,6-8\n1-2,3-3000000000\n";
+        assert_eq!(
+            Err(Day04::parse(input).unwrap_err()),
+            part_one_by_char(input)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn variants_agree_on_generated_input(input in assignments()) {
+            if Day04::parse(&input).is_err() {
+                for variant in Day04::variants() {
+                    prop_assert!((variant.solve)(&input).is_err(), "{} accepted {:?}", variant.name, input);
+                }
+                return Ok(());
+            }
+            for part in Part::ALL {
+                cross_check(&Day04::variants(), part, &input)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+            }
+        }
     }
 }
@@ -1,3 +1,11 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use aoc_common::{parse::unsigned, Error, ParseError, Solution};
+
 #[derive(Debug)]
 struct Instruction {
     move_count: usize,
@@ -5,130 +13,177 @@ struct Instruction {
     move_to: usize,
 }
 
-fn parse_instruction(input: &str) -> Instruction {
-    let numbers: Vec<usize> = input
-        .split(' ')
-        .filter_map(|c| c.parse::<usize>().ok())
-        .collect();
-    assert_eq!(3, numbers.len());
-
-    return Instruction {
-        move_count: numbers[0],
-        move_from: numbers[1],
-        move_to: numbers[2],
+fn parse_instruction(
+    input: &str,
+    line: &str,
+    num_stacks: usize,
+) -> Result<Instruction, ParseError> {
+    let words: Vec<&str> = line.split(' ').collect();
+    let ["move", count, "from", from, "to", to] = words[..] else {
+        return Err(ParseError::at(input, line, "expected `move N from A to B`"));
     };
-}
-
-pub fn part_one(input: &str) -> String {
-    let lines: Vec<&str> = input.lines().collect();
-    let mut first_blank_index: usize = 0;
 
-    for (i, line) in lines.iter().enumerate() {
-        if line.len() == 0 {
-            first_blank_index = i;
-            break;
-        }
-    }
-
-    let num_stacks = lines[first_blank_index - 1]
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .last()
-        .unwrap() as usize;
-
-    let mut stacks: Vec<Vec<char>> = Vec::new();
-    stacks.resize(num_stacks, Vec::new());
+    let stack = |text: &str| {
+        return match unsigned(input, text)? {
+            n if (1..=num_stacks).contains(&n) => Ok(n),
+            _ => Err(ParseError::at(input, text, "no such stack")),
+        };
+    };
 
-    for line in lines.iter().take(num_stacks - 1).rev() {
-        for (i, c) in line.chars().skip(1).step_by(4).enumerate() {
-            if c != ' ' {
-                stacks[i].push(c);
-            }
-        }
-    }
+    return Ok(Instruction {
+        move_count: unsigned(input, count)?,
+        move_from: stack(from)?,
+        move_to: stack(to)?,
+    });
+}
 
-    for line in lines.iter().skip(first_blank_index + 1) {
-        let instruction = parse_instruction(line);
-        for _ in 1..=instruction.move_count {
-            let moved = stacks[instruction.move_from - 1].pop().unwrap();
-            stacks[instruction.move_to - 1].push(moved);
-        }
-    }
+pub struct Crates {
+    stacks: Vec<Vec<char>>,
+    instructions: Vec<Instruction>,
+}
 
+fn top_of_stacks(stacks: Vec<Vec<char>>) -> Result<String, Error> {
     let mut top_of_stack: String = String::new();
 
     for mut stack in stacks {
-        top_of_stack.push(stack.pop().unwrap());
+        top_of_stack.push(stack.pop().ok_or("stack is empty")?);
     }
 
-    return top_of_stack;
+    return Ok(top_of_stack);
 }
 
-pub fn part_two(input: &str) -> String {
-    let lines: Vec<&str> = input.lines().collect();
-    let mut first_blank_index: usize = 0;
-
-    for (i, line) in lines.iter().enumerate() {
-        if line.len() == 0 {
-            first_blank_index = i;
-            break;
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+
+    type Input<'a> = Crates;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        let first_blank_index = lines
+            .iter()
+            .position(|line| line.is_empty())
+            .ok_or_else(|| {
+                ParseError::at_end(input, "missing blank line after the stack drawing")
+            })?;
+
+        let numbers = lines[..first_blank_index]
+            .last()
+            .ok_or_else(|| ParseError::at(input, lines[0], "missing stack drawing"))?;
+        let num_stacks = numbers
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .next_back()
+            .ok_or_else(|| ParseError::at(input, numbers, "missing stack numbers"))?
+            as usize;
+
+        let mut stacks: Vec<Vec<char>> = Vec::new();
+        stacks.resize(num_stacks, Vec::new());
+
+        for line in lines.iter().take(first_blank_index - 1).rev() {
+            for (i, (offset, c)) in line.char_indices().skip(1).step_by(4).enumerate() {
+                if c == ' ' {
+                    continue;
+                }
+                let stack = stacks.get_mut(i).ok_or_else(|| {
+                    ParseError::at(input, &line[offset..offset + c.len_utf8()], "no such stack")
+                })?;
+                stack.push(c);
+            }
         }
-    }
 
-    let num_stacks = lines[first_blank_index - 1]
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .last()
-        .unwrap() as usize;
+        let instructions = lines
+            .iter()
+            .skip(first_blank_index + 1)
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_instruction(input, line, num_stacks))
+            .collect::<Result<_, _>>()?;
+
+        return Ok(Crates {
+            stacks,
+            instructions,
+        });
+    }
 
-    let mut stacks: Vec<Vec<char>> = Vec::new();
-    stacks.resize(num_stacks, Vec::new());
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        let mut stacks = input.stacks.clone();
 
-    for line in lines.iter().take(num_stacks - 1).rev() {
-        for (i, c) in line.chars().skip(1).step_by(4).enumerate() {
-            if c != ' ' {
-                stacks[i].push(c);
+        for instruction in &input.instructions {
+            for _ in 1..=instruction.move_count {
+                let moved = stacks[instruction.move_from - 1]
+                    .pop()
+                    .ok_or("moved from an empty stack")?;
+                stacks[instruction.move_to - 1].push(moved);
             }
         }
-    }
 
-    for line in lines.iter().skip(first_blank_index + 1) {
-        let instruction = parse_instruction(line);
+        return top_of_stacks(stacks);
+    }
 
-        let mut moved: Vec<char> = Vec::new();
-        for _ in 1..=instruction.move_count {
-            moved.push(stacks[instruction.move_from - 1].pop().unwrap());
-        }
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        let mut stacks = input.stacks.clone();
+
+        for instruction in &input.instructions {
+            let mut moved: Vec<char> = Vec::new();
+            for _ in 1..=instruction.move_count {
+                moved.push(
+                    stacks[instruction.move_from - 1]
+                        .pop()
+                        .ok_or("moved from an empty stack")?,
+                );
+            }
 
-        for &item in moved.iter().rev() {
-            stacks[instruction.move_to - 1].push(item);
+            for &item in moved.iter().rev() {
+                stacks[instruction.move_to - 1].push(item);
+            }
         }
-    }
 
-    let mut top_of_stack: String = String::new();
-
-    for mut stack in stacks {
-        top_of_stack.push(stack.pop().unwrap());
+        return top_of_stacks(stacks);
     }
-
-    return top_of_stack;
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use alloc::string::ToString;
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
 
     use super::*;
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day05::variants()).unwrap();
+    }
+
     #[test]
     fn part_one_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!("CWMTGHBDW", part_one(input.as_str()));
+        let answer = cross_check(&Day05::variants(), Part::One, Day05::INPUT).unwrap();
+        assert_eq!("CWMTGHBDW", answer);
     }
 
     #[test]
     fn part_two_returns_correct_result() {
-        let input = fs::read_to_string("input.txt").expect("File input.txt should exist");
-        assert_eq!("SSCGWJCRB", part_two(input.as_str()));
+        let answer = cross_check(&Day05::variants(), Part::Two, Day05::INPUT).unwrap();
+        assert_eq!("SSCGWJCRB", answer);
+    }
+
+    #[test]
+    fn parse_skips_blank_lines_between_instructions() {
+        let input = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\n\nmove 1 from 1 to 2\n\n";
+        let answer = cross_check(&Day05::variants(), Part::One, input).unwrap();
+        assert_eq!("ND", answer);
+    }
+
+    #[test]
+    fn parse_reports_invalid_instruction() {
+        let input = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\n";
+        let err = Day05::parse(input).err().unwrap();
+        assert_eq!("line 6, column 18: no such stack: `3`", err.to_string());
     }
 }
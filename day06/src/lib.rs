@@ -1,30 +1,129 @@
-use std::collections::{BTreeMap, VecDeque};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn part_one(input: &str) -> u32 {
-    return check_by_map(input, 4);
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use alloc::collections::{BTreeMap, VecDeque};
+use aoc_common::{aoc, Error, ParseError, Solution};
+#[cfg(feature = "std")]
+use aoc_common::{Part, Streamer};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    #[cfg(feature = "std")]
+    const STREAM: Option<Streamer> = Some(|reader, part| {
+        let size = match part {
+            Part::One => 4,
+            Part::Two => 14,
+        };
+        return Ok(marker_stream(reader, size)?.to_string());
+    });
+    const VERSION: u32 = 2;
+
+    type Input<'a> = &'a str;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let stream = input.trim_end();
+        if let Some((i, c)) = stream.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            let text = &stream[i..i + c.len_utf8()];
+            return Err(ParseError::at(input, text, "expected a lowercase letter"));
+        }
+        return Ok(stream);
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return check_by_map(input, 4);
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return check_by_map(input, 14);
+    }
 }
 
-pub fn part_one_bitwise(input: &str) -> Option<usize> {
+#[aoc(day = 6, part = 1, variant = "bitwise")]
+pub fn part_one_bitwise(input: &str) -> Result<usize, Error> {
     return bitwise_marker(input, 4);
 }
 
-pub fn part_one_bitwise_alt(input: &str) -> Option<usize> {
+#[aoc(day = 6, part = 1, variant = "bitwise_alt")]
+pub fn part_one_bitwise_alt(input: &str) -> Result<usize, Error> {
     return bitwise_marker_alt(input, 4);
 }
 
-pub fn part_two(input: &str) -> u32 {
-    return check_by_map(input, 14);
-}
-
-pub fn part_two_bitwise(input: &str) -> Option<usize> {
+#[aoc(day = 6, part = 2, variant = "bitwise")]
+pub fn part_two_bitwise(input: &str) -> Result<usize, Error> {
     return bitwise_marker(input, 14);
 }
 
-pub fn part_two_bitwise_alt(input: &str) -> Option<usize> {
+#[aoc(day = 6, part = 2, variant = "bitwise_alt")]
+pub fn part_two_bitwise_alt(input: &str) -> Result<usize, Error> {
     return bitwise_marker_alt(input, 14);
 }
 
-fn check_by_map(input: &str, num: usize) -> u32 {
+#[cfg(feature = "std")]
+#[aoc(day = 6, part = 1, variant = "stream")]
+fn stream_part_one(input: &str) -> Result<usize, Error> {
+    return marker_stream(input.as_bytes(), 4);
+}
+
+#[cfg(feature = "std")]
+#[aoc(day = 6, part = 2, variant = "stream")]
+fn stream_part_two(input: &str) -> Result<usize, Error> {
+    return marker_stream(input.as_bytes(), 14);
+}
+
+/// Reads `reader` a byte at a time, remembering only the last `size`, and
+/// stops as soon as they are all different.
+#[cfg(feature = "std")]
+pub fn marker_stream(reader: impl BufRead, size: usize) -> Result<usize, Error> {
+    let mut window: VecDeque<u8> = VecDeque::with_capacity(size);
+    let mut counts = [0u8; 26];
+    let mut distinct = 0;
+
+    for (i, byte) in reader.bytes().enumerate() {
+        let byte = byte?;
+        if byte == b'\n' || byte == b'\r' {
+            break;
+        }
+        if !byte.is_ascii_lowercase() {
+            return Err(ParseError {
+                line: 1,
+                column: i + 1,
+                text: byte.escape_ascii().to_string(),
+                message: "expected a lowercase letter".to_string(),
+            }
+            .into());
+        }
+
+        if window.len() == size {
+            let oldest = window.pop_front().unwrap_or_default();
+            counts[(oldest - b'a') as usize] -= 1;
+            if counts[(oldest - b'a') as usize] == 0 {
+                distinct -= 1;
+            }
+        }
+        window.push_back(byte);
+        counts[(byte - b'a') as usize] += 1;
+        if counts[(byte - b'a') as usize] == 1 {
+            distinct += 1;
+        }
+
+        if distinct == size {
+            return Ok(i + 1);
+        }
+    }
+
+    return Err("no marker found".into());
+}
+
+fn check_by_map(input: &str, num: usize) -> Result<u32, Error> {
     let mut num_processed: u32 = 0;
     let mut sequence: VecDeque<char> = VecDeque::new();
     let mut sequence_set: BTreeMap<char, u8> = BTreeMap::new();
@@ -32,7 +131,7 @@ fn check_by_map(input: &str, num: usize) -> u32 {
     for char in input.chars() {
         if sequence.len() == num {
             if sequence_set.values().all(|v| *v == 1) {
-                return num_processed;
+                return Ok(num_processed);
             }
 
             if let Some(popped) = sequence.pop_front() {
@@ -49,84 +148,182 @@ fn check_by_map(input: &str, num: usize) -> u32 {
         num_processed += 1;
     }
 
-    return num_processed;
+    // The marker may end on the last character, which the loop never checks.
+    if sequence.len() == num && sequence_set.values().all(|v| *v == 1) {
+        return Ok(num_processed);
+    }
+    return Err("no marker found".into());
 }
 
-fn bitwise_marker(input: &str, num: usize) -> Option<usize> {
-    let bytes = input.as_bytes();
+// Both bitwise markers shift by each byte's distance from `a`, so they stop at
+// any byte that is not a lowercase letter, and check whatever follows the
+// marker before returning it.
+fn bitwise_marker(input: &str, num: usize) -> Result<usize, Error> {
+    let bytes = input.trim_end().as_bytes();
     let mut filter = 0u32;
 
-    bytes
-        .iter()
-        .take(num - 1)
-        .for_each(|c| filter ^= 1 << (c - b'a'));
-
-    return bytes
-        .windows(num)
-        .position(|set| {
-            filter ^= 1 << (set[set.len() - 1] - b'a');
-            let result = filter.count_ones() == num as u32;
-            filter ^= 1 << (set[0] - b'a');
-            return result;
-        })
-        .map(|x| x + num);
+    for &c in bytes.iter().take(num - 1) {
+        if !c.is_ascii_lowercase() {
+            return Err(stream_error(input, "expected a lowercase letter"));
+        }
+        filter ^= 1 << (c - b'a');
+    }
+
+    for (i, set) in bytes.windows(num).enumerate() {
+        let last = set[set.len() - 1];
+        if !last.is_ascii_lowercase() {
+            return Err(stream_error(input, "expected a lowercase letter"));
+        }
+        filter ^= 1 << (last - b'a');
+        if filter.count_ones() == num as u32 {
+            return after_marker(input, bytes, i + num);
+        }
+        filter ^= 1 << (set[0] - b'a');
+    }
+
+    return Err(stream_error(input, "no marker found"));
 }
 
-fn bitwise_marker_alt(input: &str, num: usize) -> Option<usize> {
-    return input
-        .as_bytes()
-        .windows(num)
-        .position(|set| {
-            let mut data: u32 = 0;
-            for &c in set {
-                let prev = data;
-                data |= 1 << (c - b'a');
-                if prev == data {
-                    return false;
-                }
+fn bitwise_marker_alt(input: &str, num: usize) -> Result<usize, Error> {
+    let bytes = input.trim_end().as_bytes();
+
+    'windows: for (i, set) in bytes.windows(num).enumerate() {
+        let mut data: u32 = 0;
+        for &c in set {
+            if !c.is_ascii_lowercase() {
+                return Err(stream_error(input, "expected a lowercase letter"));
+            }
+            let prev = data;
+            data |= 1 << (c - b'a');
+            if prev == data {
+                continue 'windows;
             }
-            return true;
-        })
-        .map(|x| x + num);
+        }
+        return after_marker(input, bytes, i + num);
+    }
+
+    return Err(stream_error(input, "no marker found"));
+}
+
+fn after_marker(input: &str, bytes: &[u8], end: usize) -> Result<usize, Error> {
+    if !bytes[end..].iter().all(u8::is_ascii_lowercase) {
+        return Err(stream_error(input, "expected a lowercase letter"));
+    }
+    return Ok(end);
+}
+
+// The error `Day06::parse` gives for `input`, or `message` if it has none, so
+// the bitwise markers reject a stream the same way the other variants do.
+fn stream_error(input: &str, message: &str) -> Error {
+    return Day06::parse(input)
+        .err()
+        .map_or_else(|| message.into(), Into::into);
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use alloc::string::{String, ToString};
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    // Noise from a five letter alphabet can hold a start-of-packet marker but
+    // never a start-of-message one, so one is spliced in at a random offset.
+    fn datastream() -> impl Strategy<Value = String> {
+        let marker = Just(b"abcdefghijklmnopqrstuvwxyz".to_vec()).prop_shuffle();
+        return (
+            vec(b'a'..=b'e', 0..100),
+            marker,
+            vec(b'a'..=b'z', 0..100),
+            any::<bool>(),
+        )
+            .prop_map(|(mut stream, marker, rest, trailing_newline)| {
+                stream.extend_from_slice(&marker[..14]);
+                stream.extend(rest);
+                if trailing_newline {
+                    stream.push(b'\n');
+                }
+                return String::from_utf8(stream).unwrap();
+            });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day06::variants()).unwrap();
     }
 
     #[test]
     fn part_one_returns_correct_result() {
-        assert_eq!(1723, part_one(&get_input()));
+        let answer = cross_check(&Day06::variants(), Part::One, Day06::INPUT).unwrap();
+        assert_eq!("1723", answer);
     }
 
     #[test]
-    fn part_one_bitwise_returns_correct_result() {
-        assert_eq!(Some(1723), part_one_bitwise(&get_input()));
+    fn part_two_returns_correct_result() {
+        let answer = cross_check(&Day06::variants(), Part::Two, Day06::INPUT).unwrap();
+        assert_eq!("3708", answer);
     }
 
     #[test]
-    fn part_one_bitwise_alt_returns_correct_result() {
-        assert_eq!(Some(1723), part_one_bitwise_alt(&get_input()));
+    fn parse_reports_invalid_character() {
+        let err = Day06::parse("mjqjpqmgbljsphdztnv\njfqwrcgsmlb\n").unwrap_err();
+        assert_eq!((1, 20, "\n"), (err.line, err.column, err.text.as_str()));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn part_two_returns_correct_result() {
-        assert_eq!(3708, part_two(&get_input()));
+    fn marker_stream_reports_invalid_character() {
+        let err = marker_stream("mjq1jpq".as_bytes(), 4).unwrap_err();
+        assert_eq!(
+            "line 1, column 4: expected a lowercase letter: `1`",
+            err.to_string()
+        );
     }
 
     #[test]
-    fn part_two_bitwise_returns_correct_result() {
-        assert_eq!(Some(3708), part_two_bitwise(&get_input()));
+    fn bitwise_rejects_what_parse_rejects() {
+        for input in ["ab1cd\n", "abcd1\n", "abcdefghijklmnop\nq\n"] {
+            let err = Day06::parse(input).unwrap_err().to_string();
+            for variant in [
+                part_one_bitwise,
+                part_one_bitwise_alt,
+                part_two_bitwise,
+                part_two_bitwise_alt,
+            ] {
+                assert_eq!(err, variant(input).unwrap_err().to_string());
+            }
+        }
     }
 
     #[test]
-    fn part_two_bitwise_alt_returns_correct_result() {
-        assert_eq!(Some(3708), part_two_bitwise_alt(&get_input()));
+    fn every_variant_rejects_streams_without_marker() {
+        for input in ["", "\n", "0\n", "aaaa\n", "abc"] {
+            for variant in Day06::variants() {
+                assert!(
+                    (variant.solve)(input).is_err(),
+                    "{} on {:?}",
+                    variant.name,
+                    input
+                );
+            }
+        }
+        assert_eq!(
+            "4",
+            cross_check(&Day06::variants(), Part::One, "abcd").unwrap()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn variants_agree_on_generated_input(input in datastream()) {
+            for part in Part::ALL {
+                cross_check(&Day06::variants(), part, &input)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+            }
+        }
     }
 }
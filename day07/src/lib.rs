@@ -1,18 +1,26 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use aoc_common::{parse::unsigned, Error, ParseError, Solution};
+#[cfg(feature = "std")]
+use aoc_common::{Answers, Generator, Rng};
+
 #[derive(Debug)]
 struct Node {
-    idx: usize,
     size: usize,
-    path: String,
     parent: Option<usize>,
     children: Vec<usize>,
 }
 
 impl Node {
-    fn new(idx: usize, path: String, size: usize) -> Self {
+    fn new(size: usize) -> Self {
         Self {
-            idx,
             size,
-            path,
             parent: None,
             children: vec![],
         }
@@ -20,72 +28,60 @@ impl Node {
 }
 
 #[derive(Debug, Default)]
-struct ArenaTree {
+pub struct ArenaTree<'a> {
     arena: Vec<Node>,
+    // Each node but the root, by its parent and name.
+    names: BTreeMap<(usize, &'a str), usize>,
 }
 
-impl ArenaTree {
-    pub fn insert(&mut self, val: &str, parent: Option<usize>, size: Option<usize>) -> usize {
+impl<'a> ArenaTree<'a> {
+    pub fn insert(&mut self, val: &'a str, parent: Option<usize>, size: Option<usize>) -> usize {
         let idx = self.arena.len();
-        let path = self.construct_path(val, parent);
-
-        self.arena.push(Node::new(idx, path, size.unwrap_or(0)));
+        self.arena.push(Node::new(size.unwrap_or(0)));
 
         if let Some(parent_node) = parent {
             self.arena[idx].parent = Some(parent_node);
             self.arena[parent_node].children.push(idx);
-        }
-
-        if let Some(_) = size {
-            self.set_size_for_parents(idx);
+            self.names.insert((parent_node, val), idx);
         }
 
         return idx;
     }
 
     pub fn node(&self, val: &str, parent: usize) -> Option<usize> {
-        let path = self.construct_path(val, Some(parent));
-        for node in &self.arena {
-            if node.path == path {
-                return Some(node.idx);
-            }
-        }
-
-        return None;
-    }
-
-    fn set_size_for_parents(&mut self, from: usize) {
-        let size = self.arena[from].size;
-        let mut trav = &mut self.arena[from];
-
-        while let Some(inner) = trav.parent {
-            trav = &mut self.arena[inner];
-            trav.size += size;
-        }
+        return self.names.get(&(parent, val)).copied();
     }
 
-    fn construct_path(&self, val: &str, parent: Option<usize>) -> String {
-        if let Some(parent_node) = parent {
-            return self.arena[parent_node].path.clone() + "/" + &val.to_string();
+    // Adds every node's size to its parent's. Children always come after
+    // their parent in the arena, so one pass from the end sees each node
+    // whole before passing it up.
+    fn total_sizes(&mut self) {
+        for idx in (0..self.arena.len()).rev() {
+            if let Some(parent) = self.arena[idx].parent {
+                self.arena[parent].size += self.arena[idx].size;
+            }
         }
-
-        return val.to_string();
     }
 }
 
-fn build_tree(input: &str) -> ArenaTree {
-    let mut tree: ArenaTree = ArenaTree::default();
+fn build_tree(input: &str) -> Result<ArenaTree<'_>, ParseError> {
+    let mut tree = ArenaTree::default();
     let mut current_node: usize = tree.insert("/", None, None);
 
-    for line in input.lines().skip(1) {
+    let mut lines = input.lines();
+    match lines.next() {
+        Some("$ cd /") => {}
+        Some(line) => return Err(ParseError::at(input, line, "expected `$ cd /`")),
+        None => return Err(ParseError::at_end(input, "expected `$ cd /`")),
+    }
+
+    for line in lines {
         if line.starts_with("$ ls") {
             continue;
         } else if line.starts_with("$ cd ..") {
-            if let Some(parent) = tree.arena[current_node].parent {
-                current_node = parent;
-            } else {
-                unreachable!();
-            }
+            current_node = tree.arena[current_node]
+                .parent
+                .ok_or_else(|| ParseError::at(input, line, "cannot leave the root directory"))?;
         } else if line.starts_with("$ cd ") {
             let dir_name = line.split_at(5).1;
 
@@ -98,67 +94,213 @@ fn build_tree(input: &str) -> ArenaTree {
         } else if line.starts_with("dir ") {
             let dir_name = line.split_at(4).1;
 
-            if let None = tree.node(dir_name, current_node) {
+            if tree.node(dir_name, current_node).is_none() {
                 tree.insert(dir_name, Some(current_node), None);
             }
+        } else if line.starts_with('$') {
+            return Err(ParseError::at(input, line, "unknown command"));
         } else {
-            let file_info: Vec<&str> = line.split(' ').collect();
-            let file_size = file_info[0].parse().unwrap();
-            let file_name = file_info[1];
-            if let None = tree.node(file_name, current_node) {
+            let (file_size, file_name) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::at(input, line, "expected `SIZE NAME`"))?;
+            let file_size = unsigned(input, file_size)?;
+            if tree.node(file_name, current_node).is_none() {
                 tree.insert(file_name, Some(current_node), Some(file_size));
             }
         }
     }
 
-    return tree;
+    tree.total_sizes();
+    return Ok(tree);
 }
 
-pub fn part_one(input: &str) -> usize {
-    let tree = build_tree(input);
+const TOTAL_SPACE: usize = 70000000;
+const MIN_SPACE: usize = 30000000;
 
-    let mut sum: usize = 0;
-    for node in tree.arena {
-        if node.children.len() > 0 && node.size < 100000 {
-            sum += node.size;
-        }
+// A directory being listed while generating, and what is left to visit in it.
+#[cfg(feature = "std")]
+struct Listing {
+    subdirs: usize,
+    visited: usize,
+    size: usize,
+    empty: bool,
+}
+
+#[cfg(feature = "std")]
+fn list_directory(
+    rng: &mut Rng,
+    budget: &mut usize,
+    min_subdirs: usize,
+    max_file_size: u64,
+    big_file: Option<usize>,
+    out: &mut dyn Write,
+) -> Result<Listing, Error> {
+    // Mostly one subdirectory, so that the tree grows deep rather than wide.
+    let subdirs = (*rng.pick(&[0, 1, 1, 1, 2, 3]))
+        .max(min_subdirs)
+        .min(*budget);
+    *budget -= subdirs;
+    let files = rng.range(0..4) as usize;
+
+    writeln!(out, "$ ls")?;
+    for i in 0..subdirs {
+        writeln!(out, "dir d{}", i)?;
+    }
+    let mut size = 0;
+    for i in 0..files {
+        let file_size = rng.range(1..max_file_size + 1) as usize;
+        writeln!(out, "{} f{}.txt", file_size, i)?;
+        size += file_size;
+    }
+    if let Some(file_size) = big_file {
+        writeln!(out, "{} big.bin", file_size)?;
+        size += file_size;
     }
 
-    return sum;
+    return Ok(Listing {
+        subdirs,
+        visited: 0,
+        size,
+        empty: subdirs + files == 0 && big_file.is_none(),
+    });
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    const TOTAL_SPACE: usize = 70000000;
-    const MIN_SPACE: usize = 30000000;
-    let tree = build_tree(input);
+/// Writes a terminal session that walks `size` nested directories depth first.
+/// One big file in the first subdirectory fills the disk enough that part two
+/// has to pick something; the rest stay small however large `size` gets.
+#[cfg(feature = "std")]
+pub fn generate(size: usize, rng: &mut Rng, out: &mut dyn Write) -> Result<Answers, Error> {
+    let max_file_size = (15_000_000 / (3 * (size + 1))).clamp(1, 300_000) as u64;
+    let mut big_file = Some(rng.range(45_000_000..55_000_000) as usize);
+    let mut budget = size;
+    // Subdirectories listed but not yet visited; while there are none left the
+    // next listing must have one, or the walk would stop short of `size`.
+    let mut pending = 0;
+    let mut small_total = 0;
+    let mut sizes = Vec::new();
+
+    writeln!(out, "$ cd /")?;
+    let root_file = if size == 0 { big_file.take() } else { None };
+    let root = list_directory(rng, &mut budget, 1, max_file_size, root_file, out)?;
+    pending += root.subdirs;
+    let mut stack = vec![root];
 
-    let used_space = tree.arena[0].size;
+    while let Some(current) = stack.last_mut() {
+        if current.visited < current.subdirs {
+            writeln!(out, "$ cd d{}", current.visited)?;
+            current.visited += 1;
+            pending -= 1;
+            let min_subdirs = usize::from(pending == 0);
+            let listing = list_directory(
+                rng,
+                &mut budget,
+                min_subdirs,
+                max_file_size,
+                big_file.take(),
+                out,
+            )?;
+            pending += listing.subdirs;
+            stack.push(listing);
+            continue;
+        }
+
+        let done = stack.pop().unwrap();
+        if !done.empty {
+            if done.size < 100000 {
+                small_total += done.size;
+            }
+            sizes.push(done.size);
+        }
+        if let Some(parent) = stack.last_mut() {
+            parent.size += done.size;
+            writeln!(out, "$ cd ..")?;
+        }
+    }
 
-    return tree
-        .arena
+    let free_space = TOTAL_SPACE - sizes.last().unwrap();
+    let smallest = sizes
         .iter()
-        .filter(|n| n.children.len() > 0 && TOTAL_SPACE - used_space + n.size > MIN_SPACE)
-        .map(|n| n.size)
-        .min();
+        .filter(|&&size| free_space + size > MIN_SPACE)
+        .min()
+        .unwrap();
+    return Ok(Answers::new(small_total, smallest));
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    #[cfg(feature = "std")]
+    const GENERATE: Option<Generator> = Some(generate);
+
+    type Input<'a> = ArenaTree<'a>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return build_tree(input);
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        let mut sum: usize = 0;
+        for node in &input.arena {
+            if !node.children.is_empty() && node.size < 100000 {
+                sum += node.size;
+            }
+        }
+
+        return Ok(sum);
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        let free_space = TOTAL_SPACE
+            .checked_sub(input.arena[0].size)
+            .ok_or("files do not fit on the disk")?;
+
+        return input
+            .arena
+            .iter()
+            .filter(|n| !n.children.is_empty() && free_space + n.size > MIN_SPACE)
+            .map(|n| n.size)
+            .min()
+            .ok_or("no directory frees enough space".into());
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    use alloc::string::ToString;
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
 
     use super::*;
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day07::variants()).unwrap();
     }
 
     #[test]
     fn part_one_computes_correct_result() {
-        assert_eq!(1297683, part_one(&get_input()));
+        let answer = cross_check(&Day07::variants(), Part::One, Day07::INPUT).unwrap();
+        assert_eq!("1297683", answer);
     }
 
     #[test]
     fn part_two_computes_correct_result() {
-        assert_eq!(Some(5756764), part_two(&get_input()));
+        let answer = cross_check(&Day07::variants(), Part::Two, Day07::INPUT).unwrap();
+        assert_eq!("5756764", answer);
+    }
+
+    #[test]
+    fn parse_reports_leaving_root() {
+        let err = Day07::parse("$ cd /\n$ ls\n14848514 b.txt\n$ cd ..\n").unwrap_err();
+        assert_eq!(
+            "line 4, column 1: cannot leave the root directory: `$ cd ..`",
+            err.to_string()
+        );
     }
 }
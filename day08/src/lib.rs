@@ -1,150 +1,155 @@
-pub fn part_one(input: &str) -> usize {
-    // Row x Col
-    let trees: Vec<Vec<u32>> = input
-        .lines()
-        .map(|l| l.chars().filter_map(|c| c.to_digit(10)).collect())
-        .collect();
-
-    let mut visible: Vec<Vec<bool>> = vec![vec![false; trees[0].len()]; trees.len()];
-
-    // left to right
-    for i in 0..trees.len() {
-        let mut tallest: u32 = trees[i][0];
-        visible[i][0] = true;
-        for j in 0..trees[0].len() {
-            if trees[i][j] > tallest {
-                visible[i][j] = true;
-                tallest = trees[i][j];
-            }
-            if trees[i][j] == 9 {
-                break;
-            }
-        }
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::io::Write;
+
+#[cfg(feature = "std")]
+use aoc_common::{Answers, Generator, Rng};
+use aoc_common::{Direction, Error, Grid, ParseError, Point, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    #[cfg(feature = "std")]
+    const GENERATE: Option<Generator> = Some(generate);
+
+    type Input<'a> = Grid<u32>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return Grid::parse(input, "expected a digit", |c| c.to_digit(10));
     }
 
-    // right to left
-    for i in 0..trees.len() {
-        let mut tallest: u32 = trees[i][trees[0].len() - 1];
-        visible[i][trees[0].len() - 1] = true;
-        for j in (0..trees[0].len()).rev() {
-            if trees[i][j] > tallest {
-                visible[i][j] = true;
-                tallest = trees[i][j];
-            }
-            if trees[i][j] == 9 {
-                break;
-            }
-        }
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return Ok(count_visible(input));
     }
 
-    // top to bottom
-    for j in 0..trees[0].len() {
-        let mut tallest: u32 = trees[0][j];
-        visible[0][j] = true;
-        for i in 0..trees.len() {
-            if trees[i][j] > tallest {
-                visible[i][j] = true;
-                tallest = trees[i][j];
-            }
-            if trees[i][j] == 9 {
-                break;
-            }
-        }
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return Ok(best_scenic_score(input));
     }
+}
 
-    // bottom to top
-    for j in 0..trees[0].len() {
-        let mut tallest: u32 = trees[trees.len() - 1][j];
-        visible[trees.len() - 1][j] = true;
-        for i in (0..trees.len()).rev() {
-            if trees[i][j] > tallest {
-                visible[i][j] = true;
-                tallest = trees[i][j];
-            }
-            if trees[i][j] == 9 {
-                break;
-            }
+/// Writes a `size` by `size` forest of random heights. The answers are not
+/// known without solving it.
+#[cfg(feature = "std")]
+pub fn generate(size: usize, rng: &mut Rng, out: &mut dyn Write) -> Result<Answers, Error> {
+    let mut row = vec![b'0'; size + 1];
+    row[size] = b'\n';
+    for _ in 0..size {
+        for tree in &mut row[..size] {
+            *tree = b'0' + rng.range(0..10) as u8;
         }
+        out.write_all(&row)?;
     }
 
-    return visible.iter().fold(0, |res, row| {
-        res + row.iter().fold(0, |result, col| {
-            if *col {
-                return result + 1;
-            }
-            return result;
-        })
-    });
+    return Ok(Answers::default());
+}
+
+// Sweeps every row and column from both edges, marking each tree taller than
+// all those before it. Nothing is taller than a 9, so a sweep stops there.
+fn count_visible(trees: &Grid<u32>) -> usize {
+    let mut visible = Grid::new(trees.width(), trees.height(), false);
+    for row in 0..trees.height() {
+        let line = (0..trees.width()).map(|col| Point::new(row, col));
+        sweep(trees, &mut visible, line.clone());
+        sweep(trees, &mut visible, line.rev());
+    }
+    for col in 0..trees.width() {
+        let line = (0..trees.height()).map(|row| Point::new(row, col));
+        sweep(trees, &mut visible, line.clone());
+        sweep(trees, &mut visible, line.rev());
+    }
+
+    return visible.iter().filter(|(_, &seen)| seen).count();
 }
 
-pub fn part_two(input: &str) -> usize {
-    let trees: Vec<Vec<u32>> = input
-        .lines()
-        .map(|l| l.chars().filter_map(|c| c.to_digit(10)).collect())
-        .collect();
-
-    let mut scores: Vec<Vec<usize>> = vec![vec![0; trees[0].len()]; trees.len()];
-
-    for row in 1..trees.len() - 1 {
-        for col in 1..trees[0].len() - 1 {
-            let current_tree = trees[row][col];
-
-            // left to right
-            let mut score: usize = 0;
-            for i in (col + 1)..trees[0].len() {
-                score += 1;
-                if trees[row][i] >= current_tree {
-                    break;
-                }
-            }
-            scores[row][col] = score;
-
-            // right to left
-            score = 0;
-            for i in (0..col).rev() {
-                score += 1;
-                if trees[row][i] >= current_tree {
-                    break;
-                }
-            }
-            scores[row][col] *= score;
-
-            // top to bottow
-            score = 0;
-            for i in (row + 1)..trees.len() {
-                score += 1;
-                if trees[i][col] >= current_tree {
-                    break;
-                }
-            }
-            scores[row][col] *= score;
-
-            // bottom to top
-            score = 0;
-            for i in (0..row).rev() {
-                score += 1;
-                if trees[i][col] >= current_tree {
-                    break;
-                }
-            }
-            scores[row][col] *= score;
+fn sweep(trees: &Grid<u32>, visible: &mut Grid<bool>, line: impl Iterator<Item = Point>) {
+    let mut tallest: Option<u32> = None;
+    for tree in line {
+        if tallest.is_none_or(|t| trees[tree] > t) {
+            visible[tree] = true;
+            tallest = Some(trees[tree]);
+        }
+        if trees[tree] == 9 {
+            break;
         }
     }
+}
 
-    return *scores
-        .iter()
-        .map(|row| row.iter().max().unwrap())
+// Counts the trees seen in each direction, up to and including the first one
+// at least as tall.
+fn best_scenic_score(trees: &Grid<u32>) -> usize {
+    return trees
+        .points()
+        .map(|tree| {
+            return Direction::ORTHOGONAL
+                .iter()
+                .map(|&direction| {
+                    let mut seen = 0;
+                    for other in trees.ray(tree, direction) {
+                        seen += 1;
+                        if trees[other] >= trees[tree] {
+                            break;
+                        }
+                    }
+                    return seen;
+                })
+                .product();
+        })
         .max()
-        .unwrap();
+        .unwrap_or(0);
 }
 
 #[cfg(test)]
 mod test {
+    use alloc::string::ToString;
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+
     use super::*;
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day08::variants()).unwrap();
+    }
+
+    #[test]
+    fn part_one_example() {
+        let input = "30373\n25512\n65332\n33549\n35390";
+        assert_eq!(
+            "21",
+            cross_check(&Day08::variants(), Part::One, input).unwrap()
+        );
+    }
+
+    #[test]
+    fn trees_rising_to_the_centre_are_all_visible() {
+        let input = "12321\n23432\n34543\n23432\n12321";
+        assert_eq!(25, count_visible(&Day08::parse(input).unwrap()));
+    }
+
     #[test]
     fn part_two_example() {
         let input = "30373\n25512\n65332\n33549\n35390";
-        assert_eq!(8, part_two(input));
+        assert_eq!(
+            "8",
+            cross_check(&Day08::variants(), Part::Two, input).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_reports_ragged_rows() {
+        let err = Day08::parse("30373\n2551\n65332\n").unwrap_err();
+        assert_eq!(
+            "line 2, column 1: expected 5 columns in every row: `2551`",
+            err.to_string()
+        );
     }
 }
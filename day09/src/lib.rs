@@ -1,44 +1,41 @@
-use std::{error::Error, fmt, num::ParseIntError, str::FromStr};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-#[derive(Debug)]
-enum Direction {
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use aoc_common::{
+    parse::{fields, unsigned},
+    Error, Grid, ParseError, Point, Solution, Visualizer,
+};
+#[cfg(feature = "std")]
+use aoc_common::{Answers, Generator, Rng};
+use core::iter;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
     Up(i32),
     Down(i32),
     Right(i32),
     Left(i32),
 }
 
-#[derive(Debug)]
-struct ParseDirectionError;
-
-impl fmt::Display for ParseDirectionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Error parsing direction")
-    }
-}
-
-impl From<ParseIntError> for ParseDirectionError {
-    fn from(_: ParseIntError) -> Self {
-        ParseDirectionError
-    }
-}
-
-impl Error for ParseDirectionError {}
-
-impl FromStr for Direction {
-    type Err = ParseDirectionError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(' ').collect();
-        let count = split[1].parse()?;
-        return match split[0] {
-            "U" => Ok(Direction::Up(count)),
-            "D" => Ok(Direction::Down(count)),
-            "R" => Ok(Direction::Right(count)),
-            "L" => Ok(Direction::Left(count)),
-            _ => Err(ParseDirectionError),
-        };
-    }
+fn parse_direction(input: &str, line: &str) -> Result<Direction, ParseError> {
+    let [direction, count] = fields(input, line, &[' '])?;
+    let count = unsigned(input, count)?;
+    return match direction {
+        "U" => Ok(Direction::Up(count)),
+        "D" => Ok(Direction::Down(count)),
+        "R" => Ok(Direction::Right(count)),
+        "L" => Ok(Direction::Left(count)),
+        _ => Err(ParseError::at(input, direction, "expected U, D, L or R")),
+    };
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
@@ -128,14 +125,13 @@ impl Rope {
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut rope = Rope::new(1);
+fn count_tail_positions(directions: &[Direction], tail_size: usize) -> usize {
+    let mut rope = Rope::new(tail_size);
     let mut tail_positions = vec![Position::default()];
 
-    input
-        .lines()
-        .map(|i| Direction::from_str(i).unwrap())
-        .for_each(|direction| tail_positions.append(&mut rope.move_direction(direction)));
+    directions
+        .iter()
+        .for_each(|&direction| tail_positions.append(&mut rope.move_direction(direction)));
 
     tail_positions.sort();
     tail_positions.dedup();
@@ -143,49 +139,138 @@ pub fn part_one(input: &str) -> usize {
     return tail_positions.len();
 }
 
-pub fn part_two(input: &str) -> usize {
+/// Writes `size` random moves of up to twenty steps. The answers are not
+/// known without simulating the rope.
+#[cfg(feature = "std")]
+pub fn generate(size: usize, rng: &mut Rng, out: &mut dyn Write) -> Result<Answers, Error> {
+    for _ in 0..size {
+        let direction = rng.pick(&['U', 'D', 'L', 'R']);
+        writeln!(out, "{} {}", direction, rng.range(1..21))?;
+    }
+
+    return Ok(Answers::default());
+}
+
+/// Draws every square the tail of the ten-knot rope visited as `#`, from `s`
+/// where it started, with the knots where the moves leave them on top: `H`
+/// for the head, then `1` to `9`.
+pub fn visualize(input: &str) -> Result<String, Error> {
+    let directions = Day09::parse(input)?;
     let mut rope = Rope::new(9);
-    let mut tail_positions = vec![Position::default()];
+    let mut visited = vec![Position::default()];
+    for &direction in &directions {
+        visited.append(&mut rope.move_direction(direction));
+    }
 
-    input
-        .lines()
-        .map(|i| Direction::from_str(i).unwrap())
-        .for_each(|direction| tail_positions.append(&mut rope.move_direction(direction)));
+    let knots: Vec<(&Position, char)> = iter::once((&rope.head, 'H'))
+        .chain(rope.tail.iter().zip('1'..='9'))
+        .collect();
+    let all = || visited.iter().chain(knots.iter().map(|&(knot, _)| knot));
+    let left = all().map(|p| p.x).min().unwrap_or_default();
+    let right = all().map(|p| p.x).max().unwrap_or_default();
+    let bottom = all().map(|p| p.y).min().unwrap_or_default();
+    let top = all().map(|p| p.y).max().unwrap_or_default();
+    let point = |p: &Position| Point::new((top - p.y) as usize, (p.x - left) as usize);
 
-    tail_positions.sort();
-    tail_positions.dedup();
+    let width = (right - left + 1) as usize;
+    let height = (top - bottom + 1) as usize;
+    let mut drawing = Grid::new(width, height, '.');
+    for position in &visited {
+        drawing[point(position)] = '#';
+    }
+    drawing[point(&Position::default())] = 's';
+    for &(knot, name) in knots.iter().rev() {
+        drawing[point(knot)] = name;
+    }
 
-    return tail_positions.len();
+    return Ok(drawing.to_string());
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    #[cfg(feature = "std")]
+    const GENERATE: Option<Generator> = Some(generate);
+    const VISUALIZE: Option<Visualizer> = Some(visualize);
+
+    type Input<'a> = Vec<Direction>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return input.lines().map(|l| parse_direction(input, l)).collect();
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return Ok(count_tail_positions(input, 1));
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return Ok(count_tail_positions(input, 9));
+    }
 }
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
+
     use super::*;
-    use std::fs;
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day09::variants()).unwrap();
+    }
+
+    #[test]
+    fn visualize_draws_tail_path_and_knots() {
+        let drawing = visualize(include_str!("../inputs/larger-example.txt")).unwrap();
+        let lines: Vec<&str> = drawing.lines().collect();
+        assert_eq!(
+            ("H.....................", "9....................."),
+            (lines[0], lines[9])
+        );
+        assert_eq!("....#......s.........#", lines[15]);
+        assert_eq!(".........########.....", lines[20]);
+        assert_eq!(36, drawing.matches(['#', 's', '9']).count());
     }
 
     #[test]
     fn part_one_computes_correct_result() {
-        assert_eq!(6023, part_one(&get_input()));
+        let answer = cross_check(&Day09::variants(), Part::One, Day09::INPUT).unwrap();
+        assert_eq!("6023", answer);
     }
 
     #[test]
     fn part_two_computes_correct_result() {
-        assert_eq!(2533, part_two(&get_input()));
+        let answer = cross_check(&Day09::variants(), Part::Two, Day09::INPUT).unwrap();
+        assert_eq!("2533", answer);
     }
 
     #[test]
     fn part_one_computes_example() {
         let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
-        assert_eq!(13, part_one(input));
+        let answer = cross_check(&Day09::variants(), Part::One, input).unwrap();
+        assert_eq!("13", answer);
     }
 
     #[test]
     fn part_two_computes_example() {
         let input = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n";
-        assert_eq!(36, part_two(input));
+        let answer = cross_check(&Day09::variants(), Part::Two, input).unwrap();
+        assert_eq!("36", answer);
+    }
+
+    #[test]
+    fn parse_reports_invalid_direction() {
+        let err = Day09::parse("R 4\nU 4\nX 3\n").unwrap_err();
+        assert_eq!(
+            "line 3, column 1: expected U, D, L or R: `X`",
+            err.to_string()
+        );
     }
 }
@@ -1,38 +1,26 @@
-use std::{error::Error, fmt, num::ParseIntError, str::FromStr};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-enum Instruction {
-    NoOp,
-    AddX(i32),
-}
+extern crate alloc;
 
-#[derive(Debug)]
-struct ParseInstructionError;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
-impl fmt::Display for ParseInstructionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Error parsing instruction")
-    }
-}
+use aoc_common::{parse::number, Error, ParseError, Solution, Visualizer};
 
-impl From<ParseIntError> for ParseInstructionError {
-    fn from(_: ParseIntError) -> Self {
-        ParseInstructionError
-    }
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    NoOp,
+    AddX(i32),
 }
 
-impl Error for ParseInstructionError {}
-
-impl FromStr for Instruction {
-    type Err = ParseInstructionError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(' ').collect();
-        return match parts[0] {
-            "noop" => Ok(Instruction::NoOp),
-            "addx" => Ok(Instruction::AddX(parts[1].parse()?)),
-            _ => Err(ParseInstructionError),
-        };
-    }
+fn parse_instruction(input: &str, line: &str) -> Result<Instruction, ParseError> {
+    return match line.split_once(' ') {
+        None if line == "noop" => Ok(Instruction::NoOp),
+        Some(("addx", x)) => Ok(Instruction::AddX(number(input, x)?)),
+        _ => Err(ParseError::at(input, line, "expected `noop` or `addx N`")),
+    };
 }
 
 enum CycleOp {
@@ -56,15 +44,14 @@ fn get_cycle_op(old_cycle: i32, new_cycle: i32) -> CycleOp {
     return CycleOp::Normal;
 }
 
-pub fn part_one(input: &str) -> i32 {
+fn signal_strength(instructions: &[Instruction]) -> i32 {
     let mut cycle: i32 = 0;
     let mut register_x: i32 = 1;
     let mut sum: i32 = 0;
 
-    input
-        .lines()
-        .filter_map(|l| Instruction::from_str(l).ok())
-        .for_each(|instruction| match instruction {
+    instructions
+        .iter()
+        .for_each(|instruction| match *instruction {
             Instruction::NoOp => {
                 let new_cycle = cycle + 1;
                 match get_cycle_op(cycle, new_cycle) {
@@ -115,16 +102,15 @@ fn draw_crt(cycle: i32, register_x: i32, crt: &mut String) {
     }
 }
 
-pub fn part_two(input: &str) -> String {
+fn render_crt(instructions: &[Instruction]) -> String {
     let mut cycle: i32 = 0;
     let mut register_x: i32 = 1;
     let mut sum: i32 = 0;
     let mut crt: String = "".to_string();
 
-    input
-        .lines()
-        .filter_map(|l| Instruction::from_str(l).ok())
-        .for_each(|instruction| match instruction {
+    instructions
+        .iter()
+        .for_each(|instruction| match *instruction {
             Instruction::NoOp => {
                 draw_crt(cycle, register_x, &mut crt);
                 let new_cycle = cycle + 1;
@@ -167,43 +153,100 @@ pub fn part_two(input: &str) -> String {
     return crt;
 }
 
+/// The CRT picture with its lit pixels filled in, so the letters stand out.
+pub fn visualize(input: &str) -> Result<String, Error> {
+    let instructions = Day10::parse(input)?;
+    return Ok(render_crt(&instructions)
+        .replace('#', "█")
+        .replace('.', " "));
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+
+    const VISUALIZE: Option<Visualizer> = Some(visualize);
+
+    type Input<'a> = Vec<Instruction>;
+    type PartOne = i32;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        return input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| parse_instruction(input, l))
+            .collect();
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return Ok(signal_strength(input));
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return Ok(render_crt(input));
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::fs;
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
 
     use super::*;
 
-    fn get_example() -> &'static str {
-        return "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\naddx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\naddx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\naddx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\naddx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\naddx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\naddx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\naddx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\naddx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\nnoop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\naddx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\nnoop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\naddx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\naddx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\nnoop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\naddx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\naddx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\naddx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop";
-    }
+    const EXAMPLE: &str = include_str!("../inputs/example.txt");
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day10::variants()).unwrap();
     }
 
     #[test]
     fn part_one_computes_example() {
-        assert_eq!(13140, part_one(get_example()));
+        let answer = cross_check(&Day10::variants(), Part::One, EXAMPLE).unwrap();
+        assert_eq!("13140", answer);
     }
 
     #[test]
     fn part_two_computes_example() {
         assert_eq!(
             "##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....\n",
-            part_two(get_example())
+            cross_check(&Day10::variants(), Part::Two, EXAMPLE).unwrap()
         );
     }
 
+    #[test]
+    fn visualize_fills_in_lit_pixels() {
+        let drawing = visualize(EXAMPLE).unwrap();
+        assert_eq!(
+            Some("██  ██  ██  ██  ██  ██  ██  ██  ██  ██  "),
+            drawing.lines().next()
+        );
+        assert_eq!(6, drawing.lines().count());
+    }
+
     #[test]
     fn part_one_computes_correct_result() {
-        assert_eq!(15020, part_one(&get_input()));
+        let answer = cross_check(&Day10::variants(), Part::One, Day10::INPUT).unwrap();
+        assert_eq!("15020", answer);
     }
 
     #[test]
     fn part_two_computes_correct_result() {
         assert_eq!(
             "####.####.#..#..##..#....###...##..###..\n#....#....#..#.#..#.#....#..#.#..#.#..#.\n###..###..#..#.#....#....#..#.#..#.#..#.\n#....#....#..#.#.##.#....###..####.###..\n#....#....#..#.#..#.#....#....#..#.#....\n####.#.....##...###.####.#....#..#.#....\n",
-            part_two(&get_input())
+            cross_check(&Day10::variants(), Part::Two, Day10::INPUT).unwrap()
         );
     }
+
+    #[test]
+    fn parse_reports_invalid_instruction() {
+        let err = Day10::parse("noop\naddx 3\naddx -z\n").err().unwrap();
+        assert_eq!((3, 6, "-z"), (err.line, err.column, err.text.as_str()));
+    }
 }
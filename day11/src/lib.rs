@@ -1,104 +1,143 @@
-use std::{collections::VecDeque, error::Error, fmt, num::ParseIntError, str::FromStr};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use evalexpr::{
-    build_operator_tree, Context, ContextWithMutableVariables, HashMapContext, Node, Value,
+extern crate alloc;
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::cmp::Reverse;
+
+use aoc_common::{
+    parse::{blocks, labelled, unsigned},
+    Error, ParseError, Solution,
 };
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Number(i64),
+}
+
+impl Operand {
+    fn value(self, old: i64) -> i64 {
+        return match self {
+            Operand::Old => old,
+            Operand::Number(n) => n,
+        };
+    }
+}
+
+/// How a monkey changes the worry level of an item it inspects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add(Operand, Operand),
+    Multiply(Operand, Operand),
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it overflows.
+    pub fn apply(self, old: i64) -> Option<i64> {
+        return match self {
+            Operation::Add(a, b) => a.value(old).checked_add(b.value(old)),
+            Operation::Multiply(a, b) => a.value(old).checked_mul(b.value(old)),
+        };
+    }
+}
+
+fn parse_operation(input: &str, operation: &str) -> Result<Operation, ParseError> {
+    let invalid = || {
+        let message = "expected `new = a + b` or `new = a * b`, with `old` or numbers";
+        return ParseError::at(input, operation, message);
+    };
+    let operand = |text: &str| {
+        return match text {
+            "old" => Ok(Operand::Old),
+            _ => text.parse().map(Operand::Number).map_err(|_| invalid()),
+        };
+    };
+
+    let Some(expression) = operation.strip_prefix("new =") else {
+        return Err(ParseError::at(input, operation, "expected `new = ...`"));
+    };
+    let terms: Vec<&str> = expression.split_whitespace().collect();
+    let [a, operator, b] = terms[..] else {
+        return Err(invalid());
+    };
+    return match operator {
+        "+" => Ok(Operation::Add(operand(a)?, operand(b)?)),
+        "*" => Ok(Operation::Multiply(operand(a)?, operand(b)?)),
+        _ => Err(invalid()),
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
     pub items: VecDeque<i64>,
-    pub operation: Node,
+    pub operation: Operation,
     pub divisible_by: i64,
     pub throw_to: usize,
     pub throw_to_false: usize,
     pub inspection_count: usize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseMonkeyError;
+fn parse_monkey(input: &str, notes: &str, monkey_count: usize) -> Result<Monkey, ParseError> {
+    let lines: Vec<&str> = notes.lines().collect();
+    let [_, items, operation, test, if_true, if_false] = lines[..] else {
+        let line = lines.last().unwrap_or(&notes);
+        return Err(ParseError::at(
+            input,
+            line,
+            "expected six lines of notes per monkey",
+        ));
+    };
 
-impl fmt::Display for ParseMonkeyError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Error parsing instruction")
-    }
-}
+    let items = labelled(input, items, "Starting items:")?;
+    let items = match items {
+        "" => VecDeque::new(),
+        _ => items
+            .split(", ")
+            .map(|x| unsigned(input, x))
+            .collect::<Result<_, _>>()?,
+    };
 
-impl From<ParseIntError> for ParseMonkeyError {
-    fn from(_: ParseIntError) -> Self {
-        ParseMonkeyError
-    }
-}
+    let operation = parse_operation(input, labelled(input, operation, "Operation:")?)?;
 
-impl Error for ParseMonkeyError {}
-
-impl FromStr for Monkey {
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<&str> = s.lines().filter(|l| !l.is_empty()).collect();
-        assert_eq!(6, lines.len());
-
-        return Ok(Monkey {
-            items: lines[1]
-                .split(':')
-                .nth(1)
-                .ok_or(ParseMonkeyError)?
-                .trim()
-                .split(", ")
-                .filter_map(|x| x.parse().ok())
-                .collect(),
-            operation: build_operator_tree(
-                lines[2].split(':').nth(1).ok_or(ParseMonkeyError)?.trim(),
-            )
-            .unwrap(),
-            divisible_by: lines[3]
-                .split(' ')
-                .last()
-                .ok_or(ParseMonkeyError)?
-                .parse()?,
-            throw_to: lines[4]
-                .split(' ')
-                .last()
-                .ok_or(ParseMonkeyError)?
-                .parse()?,
-            throw_to_false: lines[5]
-                .split(' ')
-                .last()
-                .ok_or(ParseMonkeyError)?
-                .parse()?,
-            inspection_count: 0,
-        });
+    let divisible_by = unsigned(input, labelled(input, test, "Test: divisible by")?)?;
+    if divisible_by == 0 {
+        return Err(ParseError::at(
+            input,
+            test,
+            "cannot test divisibility by zero",
+        ));
     }
-}
 
-pub fn part_one(input: &str) -> Result<usize, Box<dyn Error>> {
-    let mut monkeys: Vec<Monkey> = Vec::default();
-    let mut unparsed_monkey: String = String::default();
-
-    for l in input.lines() {
-        if l.is_empty() && unparsed_monkey.len() != 0 {
-            monkeys.push(Monkey::from_str(&unparsed_monkey)?);
-            unparsed_monkey.clear();
-        } else {
-            unparsed_monkey.push('\n');
-            unparsed_monkey.push_str(l);
-        }
-    }
+    let throw_to = |line: &str, label: &str| {
+        let target = labelled(input, line, label)?;
+        return match unsigned(input, target)? {
+            n if n < monkey_count => Ok(n),
+            _ => Err(ParseError::at(input, target, "no such monkey")),
+        };
+    };
 
-    if unparsed_monkey.len() > 0 {
-        monkeys.push(Monkey::from_str(&unparsed_monkey)?);
-    }
+    return Ok(Monkey {
+        items,
+        operation,
+        divisible_by,
+        throw_to: throw_to(if_true, "If true: throw to monkey")?,
+        throw_to_false: throw_to(if_false, "If false: throw to monkey")?,
+        inspection_count: 0,
+    });
+}
+
+fn monkey_business_one(input: &[Monkey]) -> Result<usize, Error> {
+    let mut monkeys = input.to_vec();
 
     for _ in 0..20 {
         for monkey_idx in 0..monkeys.len() {
             while let Some(item) = monkeys[monkey_idx].items.pop_front() {
-                let mut context = HashMapContext::new();
-                context.set_value("old".to_string(), Value::from(item))?;
-                let _ = &monkeys[monkey_idx]
+                let new_worry_level = monkeys[monkey_idx]
                     .operation
-                    .eval_empty_with_context_mut(&mut context)?;
-                let new_worry_level =
-                    (context.get_value("new").unwrap().as_int()? as f64 / 3.0).floor() as i64;
+                    .apply(item)
+                    .ok_or("worry level overflowed")?
+                    / 3;
 
                 if new_worry_level % monkeys[monkey_idx].divisible_by == 0 {
                     let throw_to = monkeys[monkey_idx].throw_to;
@@ -113,7 +152,7 @@ pub fn part_one(input: &str) -> Result<usize, Box<dyn Error>> {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspection_count.cmp(&a.inspection_count));
+    monkeys.sort_by_key(|m| Reverse(m.inspection_count));
 
     return Ok(monkeys
         .iter()
@@ -121,36 +160,19 @@ pub fn part_one(input: &str) -> Result<usize, Box<dyn Error>> {
         .fold(1, |res, m| res * m.inspection_count));
 }
 
-pub fn part_two(input: &str) -> Result<usize, Box<dyn Error>> {
-    let mut monkeys: Vec<Monkey> = Vec::default();
-    let mut unparsed_monkey: String = String::default();
-
-    for l in input.lines() {
-        if l.is_empty() && unparsed_monkey.len() != 0 {
-            monkeys.push(Monkey::from_str(&unparsed_monkey)?);
-            unparsed_monkey.clear();
-        } else {
-            unparsed_monkey.push('\n');
-            unparsed_monkey.push_str(l);
-        }
-    }
-
-    if unparsed_monkey.len() > 0 {
-        monkeys.push(Monkey::from_str(&unparsed_monkey)?);
-    }
+fn monkey_business_two(input: &[Monkey]) -> Result<usize, Error> {
+    let mut monkeys = input.to_vec();
 
     let supermod = monkeys.iter().fold(1, |res, m| res * m.divisible_by);
 
     for _ in 0..10000 {
         for monkey_idx in 0..monkeys.len() {
             while let Some(item) = monkeys[monkey_idx].items.pop_front() {
-                let mut context = HashMapContext::new();
-                context.set_value("old".to_string(), Value::from(item))?;
-                let _ = &monkeys[monkey_idx]
+                let new_worry_level = monkeys[monkey_idx]
                     .operation
-                    .eval_empty_with_context_mut(&mut context)?;
-                let mut new_worry_level = context.get_value("new").unwrap().as_int()?;
-                new_worry_level %= supermod;
+                    .apply(item)
+                    .ok_or("worry level overflowed")?
+                    % supermod;
 
                 if new_worry_level % monkeys[monkey_idx].divisible_by == 0 {
                     let throw_to = monkeys[monkey_idx].throw_to;
@@ -165,8 +187,7 @@ pub fn part_two(input: &str) -> Result<usize, Box<dyn Error>> {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspection_count.cmp(&a.inspection_count));
-    println!("\n{:?}", monkeys);
+    monkeys.sort_by_key(|m| Reverse(m.inspection_count));
 
     return Ok(monkeys
         .iter()
@@ -174,23 +195,79 @@ pub fn part_two(input: &str) -> Result<usize, Box<dyn Error>> {
         .fold(1, |res, m| res * m.inspection_count));
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    const VERSION: u32 = 2;
+
+    type Input<'a> = Vec<Monkey>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let notes: Vec<&str> = blocks(input).collect();
+        return notes
+            .iter()
+            .map(|monkey| parse_monkey(input, monkey, notes.len()))
+            .collect();
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return monkey_business_one(input);
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        return monkey_business_two(input);
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::fs;
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
 
     use super::*;
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day11::variants()).unwrap();
     }
 
     #[test]
     fn part_one_computes_correct_result() {
-        assert_eq!(0, part_one(&get_input()).unwrap());
+        let answer = cross_check(&Day11::variants(), Part::One, Day11::INPUT).unwrap();
+        assert_eq!("98280", answer);
     }
 
     #[test]
     fn part_two_computes_correct_result() {
-        assert_eq!(0, part_two(&get_input()).unwrap());
+        let answer = cross_check(&Day11::variants(), Part::Two, Day11::INPUT).unwrap();
+        assert_eq!("17673687232", answer);
+    }
+
+    #[test]
+    fn parse_operation_reads_sums_and_products() {
+        let input = "new = old * old\nnew = old + 6\nnew = old - 6\n";
+        let lines: Vec<&str> = input.lines().collect();
+        let square = parse_operation(input, lines[0]).unwrap();
+        assert_eq!(Operation::Multiply(Operand::Old, Operand::Old), square);
+        assert_eq!(Some(81), square.apply(9));
+        assert_eq!(None, square.apply(i64::MAX));
+        assert_eq!(Some(15), parse_operation(input, lines[1]).unwrap().apply(9));
+        assert!(parse_operation(input, lines[2]).is_err());
+    }
+
+    #[test]
+    fn parse_reports_invalid_operation() {
+        let input = "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old * (\n  Test: divisible by 23\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n";
+        let err = Day11::parse(input).unwrap_err();
+        assert_eq!(
+            (3, 14, "new = old * ("),
+            (err.line, err.column, err.text.as_str())
+        );
     }
 }
@@ -1,26 +1,38 @@
-use std::collections::BinaryHeap;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-#[derive(PartialEq, Eq, Clone)]
-struct Position {
-    row: usize,
-    col: usize,
-}
+extern crate alloc;
 
-impl Position {
-    pub fn new(row: usize, col: usize) -> Self {
-        Position { row, col }
-    }
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use alloc::{
+    collections::{BinaryHeap, VecDeque},
+    format,
+    string::{String, ToString},
+};
+#[cfg(feature = "std")]
+use aoc_common::{Answers, Generator, Rng};
+use aoc_common::{Error, Grid, ParseError, Point, Solution, Visualizer};
+use core::cmp::Ordering;
+
+/// Elevations from `a` to `z`, with the start and end squares already
+/// replaced by their elevations.
+#[derive(Debug)]
+pub struct Heightmap {
+    elevation: Grid<u8>,
+    start: Point,
+    end: Point,
 }
 
 #[derive(PartialEq, Eq)]
 struct Node {
     cost: usize,
-    position: Position,
+    position: Point,
     dist_sq: usize,
 }
 
 impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> Ordering {
         // invert ordering so BinaryHeap becomes a min-heap
         return other
             .cost
@@ -30,60 +42,30 @@ impl Ord for Node {
 }
 
 impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         return Some(self.cmp(other));
     }
 }
 
-pub(crate) fn distance_square(start: &Position, end: &Position) -> usize {
-    return (end.row - start.row).pow(2) + (end.col - start.col).pow(2);
+pub(crate) fn distance_square(start: &Point, end: &Point) -> usize {
+    return end.row.abs_diff(start.row).pow(2) + end.col.abs_diff(start.col).pow(2);
 }
 
-pub(crate) fn get_position_for_marker(topography: &Vec<Vec<u8>>, character: u8) -> Position {
-    let pos = topography
-        .iter()
-        .flatten()
-        .position(|c| *c == character)
-        .unwrap();
-    return Position::new(pos / topography[0].len(), pos % topography[0].len());
-}
-
-fn build_neighbors(position: &Position, max_row: usize, max_col: usize) -> Vec<Position> {
-    let mut neighbors = Vec::default();
-    if position.row != 0 {
-        neighbors.push(Position::new(position.row - 1, position.col));
-    }
-    if position.row != max_row {
-        neighbors.push(Position::new(position.row + 1, position.col));
-    }
-    if position.col != 0 {
-        neighbors.push(Position::new(position.row, position.col - 1));
-    }
-    if position.col != max_col {
-        neighbors.push(Position::new(position.row, position.col + 1));
-    }
-
-    return neighbors;
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    let topography: Vec<Vec<u8>> = input.lines().map(|l| l.bytes().collect()).collect();
-    let mut dist: Vec<Vec<usize>> = topography
-        .iter()
-        .map(|x| x.iter().map(|_| usize::MAX).collect())
-        .collect();
-
-    let start = get_position_for_marker(&topography, b'S');
-    let end = get_position_for_marker(&topography, b'E');
-
+// Fewest steps from any of `starts` to the end, climbing at most one step of
+// elevation at a time.
+fn shortest_path(map: &Heightmap, starts: impl Iterator<Item = Point>) -> Option<usize> {
+    let elevation = &map.elevation;
+    let mut dist = Grid::new(elevation.width(), elevation.height(), usize::MAX);
     let mut heap = BinaryHeap::new();
 
-    dist[start.row][start.col] = 0;
-    heap.push(Node {
-        cost: 0,
-        position: start.clone(),
-        dist_sq: distance_square(&start, &end),
-    });
+    for start in starts {
+        dist[start] = 0;
+        heap.push(Node {
+            cost: 0,
+            position: start,
+            dist_sq: distance_square(&start, &map.end),
+        });
+    }
 
     while let Some(Node {
         cost,
@@ -91,28 +73,27 @@ pub fn part_one(input: &str) -> Option<usize> {
         dist_sq: _,
     }) = heap.pop()
     {
-        if position == end {
+        if position == map.end {
             return Some(cost);
         }
 
-        if cost > dist[position.row][position.col] {
+        if cost > dist[position] {
             continue;
         }
 
-        for neighbor in build_neighbors(&position, topography.len(), topography[0].len()) {
-            // cannot traverse when elevation > 1
-            if topography[neighbor.row][neighbor.col] - topography[position.row][position.col] > 1 {
+        for neighbor in elevation.neighbors(position) {
+            if elevation[neighbor] > elevation[position] + 1 {
                 continue;
             }
 
             let next = Node {
-                cost: cost + 1, // validate this
-                dist_sq: distance_square(&neighbor, &end),
+                cost: cost + 1,
+                dist_sq: distance_square(&neighbor, &map.end),
                 position: neighbor,
             };
 
-            if next.cost < dist[next.position.row][next.position.col] {
-                dist[next.position.row][next.position.col] = next.cost;
+            if next.cost < dist[neighbor] {
+                dist[neighbor] = next.cost;
                 heap.push(next);
             }
         }
@@ -121,34 +102,192 @@ pub fn part_one(input: &str) -> Option<usize> {
     return None;
 }
 
-pub fn part_two(_input: &str) -> i32 {
-    todo!()
+/// Writes a `size` by `size` slope, at least 26 wide, rising from `a` on the
+/// left to `z` on the right one column at a time. Random squares past the
+/// foothills drop to `a` and can only be climbed out of back down, so the
+/// fastest way up is straight along the row holding `S` and `E`.
+#[cfg(feature = "std")]
+pub fn generate(size: usize, rng: &mut Rng, out: &mut dyn Write) -> Result<Answers, Error> {
+    let width = size.max(26);
+    let height = size.max(1);
+    let slope = |col: usize| (col * 25 / (width - 1)) as u8;
+    let path = rng.index(height);
+
+    let mut row = vec![b'a'; width + 1];
+    row[width] = b'\n';
+    for r in 0..height {
+        for (col, square) in row[..width].iter_mut().enumerate() {
+            *square = match slope(col) {
+                3.. if r != path && rng.one_in(8) => b'a',
+                elevation => b'a' + elevation,
+            };
+        }
+        if r == path {
+            row[0] = b'S';
+            row[width - 1] = b'E';
+        }
+        out.write_all(&row)?;
+    }
+
+    let foothills = (0..width).take_while(|&col| slope(col) == 0).count();
+    return Ok(Answers::new(width - 1, width - foothills));
+}
+
+/// Draws one of the fewest-steps routes from `S` to `E` in arrows, the way
+/// the puzzle does, with every square off the route left as `.`.
+pub fn visualize(input: &str) -> Result<String, Error> {
+    let map = Day12::parse(input)?;
+    let elevation = &map.elevation;
+
+    // Breadth first from the start, remembering where each square was reached
+    // from.
+    let mut came_from: Grid<Option<Point>> = Grid::new(elevation.width(), elevation.height(), None);
+    came_from[map.start] = Some(map.start);
+    let mut queue = VecDeque::from([map.start]);
+    while let Some(position) = queue.pop_front() {
+        if position == map.end {
+            break;
+        }
+        for neighbor in elevation.neighbors(position) {
+            if came_from[neighbor].is_none() && elevation[neighbor] <= elevation[position] + 1 {
+                came_from[neighbor] = Some(position);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    let mut drawing = Grid::new(elevation.width(), elevation.height(), '.');
+    drawing[map.end] = 'E';
+    let mut position = map.end;
+    while position != map.start {
+        let previous = came_from[position].ok_or("no path from start to end")?;
+        drawing[previous] = match (
+            position.row.cmp(&previous.row),
+            position.col.cmp(&previous.col),
+        ) {
+            (Ordering::Less, _) => '^',
+            (Ordering::Greater, _) => 'v',
+            (_, Ordering::Less) => '<',
+            _ => '>',
+        };
+        position = previous;
+    }
+
+    return Ok(drawing.to_string());
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const INPUT: &'static str = include_str!("../inputs/default.txt");
+    #[cfg(feature = "std")]
+    const GENERATE: Option<Generator> = Some(generate);
+    const VISUALIZE: Option<Visualizer> = Some(visualize);
+
+    type Input<'a> = Heightmap;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let mut elevation = Grid::parse(input, "expected an elevation, `S` or `E`", |c| {
+            return matches!(c, 'a'..='z' | 'S' | 'E').then_some(c as u8);
+        })?;
+
+        for marker in ["S", "E"] {
+            let mut found = input
+                .lines()
+                .flat_map(|l| l.match_indices(marker).map(|(i, _)| &l[i..i + 1]));
+            if found.next().is_none() {
+                return Err(ParseError::at_end(input, format!("missing `{}`", marker)));
+            }
+            if let Some(duplicate) = found.next() {
+                return Err(ParseError::at(
+                    input,
+                    duplicate,
+                    format!("more than one `{}`", marker),
+                ));
+            }
+        }
+
+        let start = elevation.position(|&c| c == b'S').unwrap_or_default();
+        let end = elevation.position(|&c| c == b'E').unwrap_or_default();
+        elevation[start] = b'a';
+        elevation[end] = b'z';
+
+        return Ok(Heightmap {
+            elevation,
+            start,
+            end,
+        });
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Result<Self::PartOne, Error> {
+        return shortest_path(input, core::iter::once(input.start))
+            .ok_or("no path from start to end".into());
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Result<Self::PartTwo, Error> {
+        let lowest = input
+            .elevation
+            .iter()
+            .filter(|&(_, &e)| e == b'a')
+            .map(|(point, _)| point);
+        return shortest_path(input, lowest).ok_or("no path from the lowest squares to end".into());
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::fs;
+    #[cfg(feature = "std")]
+    use aoc_common::check_known_inputs;
+    use aoc_common::{cross_check, Part};
 
     use super::*;
 
-    fn get_input() -> String {
-        return fs::read_to_string("input.txt").expect("File input.txt should exist");
+    #[cfg(feature = "std")]
+    #[test]
+    fn known_inputs_give_known_answers() {
+        check_known_inputs(env!("CARGO_MANIFEST_DIR"), &Day12::variants()).unwrap();
     }
 
     #[test]
     fn determines_row_col_correctly() {
-        let topography: Vec<Vec<u8>> = get_input().lines().map(|l| l.bytes().collect()).collect();
-        let start = get_position_for_marker(&topography, b'S');
-        assert_eq!(20, start.row);
-        assert_eq!(0, start.col);
+        let map = Day12::parse(Day12::INPUT).unwrap();
+        assert_eq!(20, map.start.row);
+        assert_eq!(0, map.start.col);
     }
 
     #[test]
     fn determines_correct_distance() {
-        let topography: Vec<Vec<u8>> = get_input().lines().map(|l| l.bytes().collect()).collect();
-        let start = get_position_for_marker(&topography, b'S');
-        let end = get_position_for_marker(&topography, b'E');
+        let map = Day12::parse(Day12::INPUT).unwrap();
+        assert_eq!(43 * 43, distance_square(&map.start, &map.end));
+    }
+
+    #[test]
+    fn part_one_climbs_down_and_stays_on_the_map() {
+        let input = "SbcbcdefghijklmnopqrstuvwxyE";
+        let answer = cross_check(&Day12::variants(), Part::One, input).unwrap();
+        assert_eq!("27", answer);
+    }
 
-        assert_eq!(43 * 43, distance_square(&start, &end));
+    #[test]
+    fn part_two_starts_from_any_lowest_square() {
+        let input = "EzyxwvutsrqponmlkjihgfedcbaS";
+        let answer = cross_check(&Day12::variants(), Part::Two, input).unwrap();
+        assert_eq!("26", answer);
+    }
+
+    #[test]
+    fn visualize_draws_shortest_route() {
+        let drawing = visualize(include_str!("../inputs/example.txt")).unwrap();
+        assert_eq!(31, drawing.matches(['^', 'v', '<', '>']).count());
+        assert_eq!(Some(".v.v>E^^"), drawing.lines().nth(2));
+    }
+
+    #[test]
+    fn parse_reports_missing_end() {
+        let err = Day12::parse("Sabqponm\nabcryxxl\naccszzxk\n").unwrap_err();
+        assert_eq!("line 4, column 1: missing `E`", err.to_string());
     }
 }